@@ -0,0 +1,82 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#![feature(test)]
+
+extern crate test;
+use micromap::Map;
+use test::Bencher;
+
+/// How many different keys are in the map.
+const KEYS: u32 = 32;
+
+/// Make a sequence of keys, where the key of rank `r` is looked up
+/// with the probability proportional to `1 / r^s`, according to
+/// the Zipf distribution. The ranks are spread over the keys, so the
+/// most popular ones are not the first ones inserted.
+fn zipf(s: f64, total: usize) -> Vec<u32> {
+    let weights: Vec<f64> = (1..=KEYS).map(|r| 1.0 / f64::from(r).powf(s)).collect();
+    let sum: f64 = weights.iter().sum();
+    let mut seed: u64 = 42;
+    (0..total)
+        .map(|_| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let mut x = (seed >> 11) as f64 / (1u64 << 53) as f64 * sum;
+            let mut rank = 0;
+            while rank < KEYS - 1 && x >= weights[rank as usize] {
+                x -= weights[rank as usize];
+                rank += 1;
+            }
+            (KEYS - 1 - rank) * 7 % KEYS
+        })
+        .collect()
+}
+
+fn map() -> Map<u32, u64, 32> {
+    (0..KEYS).map(|k| (k, u64::from(k))).collect()
+}
+
+macro_rules! lookup {
+    ($name:ident, $s:expr, $get:ident) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let keys = zipf($s, 1024);
+            #[allow(unused_mut)]
+            let mut m = map();
+            b.iter(|| {
+                for k in &keys {
+                    test::black_box(m.$get(k));
+                }
+            });
+        }
+    };
+}
+
+lookup!(zipf_1_get, 1.0, get);
+lookup!(zipf_1_get_and_transpose, 1.0, get_and_transpose);
+lookup!(zipf_1_get_and_move_to_front, 1.0, get_and_move_to_front);
+lookup!(zipf_2_get, 2.0, get);
+lookup!(zipf_2_get_and_transpose, 2.0, get_and_transpose);
+lookup!(zipf_2_get_and_move_to_front, 2.0, get_and_move_to_front);
+lookup!(zipf_3_get, 3.0, get);
+lookup!(zipf_3_get_and_transpose, 3.0, get_and_transpose);
+lookup!(zipf_3_get_and_move_to_front, 3.0, get_and_move_to_front);
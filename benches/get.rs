@@ -0,0 +1,74 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#![feature(test)]
+
+extern crate test;
+use micromap::Map;
+use test::Bencher;
+
+#[bench]
+fn get_from_full_map(b: &mut Bencher) {
+    let mut m: Map<u64, u64, 64> = Map::new();
+    for i in 0..64 {
+        m.insert(i, i);
+    }
+    b.iter(|| {
+        for i in 0..64 {
+            test::black_box(m.get(&i));
+        }
+    });
+}
+
+#[bench]
+fn get_after_removals(b: &mut Bencher) {
+    let mut m: Map<u64, u64, 64> = Map::new();
+    for i in 0..64 {
+        m.insert(i, i);
+    }
+    for i in 0..64 {
+        if i % 7 != 0 {
+            m.remove(&i);
+        }
+    }
+    b.iter(|| {
+        for i in 0..64 {
+            test::black_box(m.get(&i));
+        }
+    });
+}
+
+#[bench]
+fn iterate_after_removals(b: &mut Bencher) {
+    let mut m: Map<u64, u64, 64> = Map::new();
+    for i in 0..64 {
+        m.insert(i, i);
+    }
+    for i in 0..64 {
+        if i % 7 != 0 {
+            m.remove(&i);
+        }
+    }
+    b.iter(|| {
+        for _ in 0..1000 {
+            test::black_box(m.values().sum::<u64>());
+        }
+    });
+}
@@ -0,0 +1,73 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#![feature(test)]
+
+extern crate test;
+use micromap::{Map, SplitMap};
+use test::Bencher;
+
+macro_rules! lookup {
+    ($name:ident, $map:ident, $size:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let mut m: $map<u32, [u8; $size], 16> = $map::new();
+            for i in 0..16 {
+                m.insert(i, [i as u8; $size]);
+            }
+            b.iter(|| {
+                for i in 0..32 {
+                    test::black_box(m.contains_key(&test::black_box(i)));
+                }
+            });
+        }
+    };
+}
+
+macro_rules! scan {
+    ($name:ident, $map:ident, $size:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let maps: Vec<$map<u32, [u8; $size], 16>> = (0..256)
+                .map(|_| (0..16).map(|i| (i, [i as u8; $size])).collect())
+                .collect();
+            b.iter(|| {
+                for m in &maps {
+                    test::black_box(m.contains_key(&test::black_box(42)));
+                }
+            });
+        }
+    };
+}
+
+lookup!(pairs_with_8_byte_values, Map, 8);
+lookup!(split_with_8_byte_values, SplitMap, 8);
+lookup!(pairs_with_64_byte_values, Map, 64);
+lookup!(split_with_64_byte_values, SplitMap, 64);
+lookup!(pairs_with_256_byte_values, Map, 256);
+lookup!(split_with_256_byte_values, SplitMap, 256);
+lookup!(pairs_with_1024_byte_values, Map, 1024);
+lookup!(split_with_1024_byte_values, SplitMap, 1024);
+scan!(many_pairs_with_8_byte_values, Map, 8);
+scan!(many_split_with_8_byte_values, SplitMap, 8);
+scan!(many_pairs_with_64_byte_values, Map, 64);
+scan!(many_split_with_64_byte_values, SplitMap, 64);
+scan!(many_pairs_with_256_byte_values, Map, 256);
+scan!(many_split_with_256_byte_values, SplitMap, 256);
@@ -0,0 +1,111 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// The keys here are compared many at a time only with the "simd" feature:
+// $ cargo bench --bench simd --features simd
+//
+// The "interleaved" rows show that a Map with values taking space
+// compares its keys one by one, even with the feature.
+
+#![feature(test)]
+
+extern crate test;
+use micromap::{Map, SplitMap};
+use test::Bencher;
+
+/// A key, which is not a primitive integer, so it's always compared
+/// one by one, as a baseline.
+#[derive(PartialEq, Eq, Clone, Copy)]
+struct Scalar(u32);
+
+/// A primitive key, which is compared many at a time.
+const fn plain(i: u32) -> u32 {
+    i
+}
+
+/// A value, which takes no space, so that the keys in [`Map`] are
+/// packed together and may be compared many at a time.
+const fn empty(_: u32) {}
+
+/// A value, which takes space, as in most maps.
+fn wide(i: u32) -> u64 {
+    u64::from(i)
+}
+
+macro_rules! lookup {
+    ($name:ident, $map:ident, $key:expr, $value:expr, $capacity:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let mut m: $map<_, _, $capacity> = $map::new();
+            let n: u32 = $capacity;
+            for i in 0..n {
+                m.insert($key(i), $value(i));
+            }
+            b.iter(|| {
+                for i in 0..=n {
+                    test::black_box(m.get(&$key(test::black_box(i))));
+                }
+            });
+        }
+    };
+}
+
+lookup!(scalar_1, Map, Scalar, empty, 1);
+lookup!(simd_1, Map, plain, empty, 1);
+lookup!(scalar_2, Map, Scalar, empty, 2);
+lookup!(simd_2, Map, plain, empty, 2);
+lookup!(scalar_4, Map, Scalar, empty, 4);
+lookup!(simd_4, Map, plain, empty, 4);
+lookup!(scalar_8, Map, Scalar, empty, 8);
+lookup!(simd_8, Map, plain, empty, 8);
+lookup!(scalar_16, Map, Scalar, empty, 16);
+lookup!(simd_16, Map, plain, empty, 16);
+lookup!(scalar_32, Map, Scalar, empty, 32);
+lookup!(simd_32, Map, plain, empty, 32);
+lookup!(scalar_64, Map, Scalar, empty, 64);
+lookup!(simd_64, Map, plain, empty, 64);
+lookup!(interleaved_scalar_1, Map, Scalar, wide, 1);
+lookup!(interleaved_plain_1, Map, plain, wide, 1);
+lookup!(interleaved_scalar_2, Map, Scalar, wide, 2);
+lookup!(interleaved_plain_2, Map, plain, wide, 2);
+lookup!(interleaved_scalar_4, Map, Scalar, wide, 4);
+lookup!(interleaved_plain_4, Map, plain, wide, 4);
+lookup!(interleaved_scalar_8, Map, Scalar, wide, 8);
+lookup!(interleaved_plain_8, Map, plain, wide, 8);
+lookup!(interleaved_scalar_16, Map, Scalar, wide, 16);
+lookup!(interleaved_plain_16, Map, plain, wide, 16);
+lookup!(interleaved_scalar_32, Map, Scalar, wide, 32);
+lookup!(interleaved_plain_32, Map, plain, wide, 32);
+lookup!(interleaved_scalar_64, Map, Scalar, wide, 64);
+lookup!(interleaved_plain_64, Map, plain, wide, 64);
+lookup!(split_scalar_1, SplitMap, Scalar, wide, 1);
+lookup!(split_simd_1, SplitMap, plain, wide, 1);
+lookup!(split_scalar_2, SplitMap, Scalar, wide, 2);
+lookup!(split_simd_2, SplitMap, plain, wide, 2);
+lookup!(split_scalar_4, SplitMap, Scalar, wide, 4);
+lookup!(split_simd_4, SplitMap, plain, wide, 4);
+lookup!(split_scalar_8, SplitMap, Scalar, wide, 8);
+lookup!(split_simd_8, SplitMap, plain, wide, 8);
+lookup!(split_scalar_16, SplitMap, Scalar, wide, 16);
+lookup!(split_simd_16, SplitMap, plain, wide, 16);
+lookup!(split_scalar_32, SplitMap, Scalar, wide, 32);
+lookup!(split_simd_32, SplitMap, plain, wide, 32);
+lookup!(split_scalar_64, SplitMap, Scalar, wide, 64);
+lookup!(split_simd_64, SplitMap, plain, wide, 64);
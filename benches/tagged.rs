@@ -0,0 +1,55 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#![feature(test)]
+
+extern crate test;
+use micromap::{Map, TaggedMap};
+use test::Bencher;
+
+/// Keys with a long common prefix, like the ones in configuration files.
+fn key(i: usize) -> String {
+    format!("application.settings.section.{i}")
+}
+
+macro_rules! lookup {
+    ($name:ident, $map:ty, $capacity:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let m: $map = (0..$capacity).map(|i| (key(i), i)).collect();
+            let keys: Vec<String> = (0..$capacity).map(key).collect();
+            b.iter(|| {
+                for k in &keys {
+                    test::black_box(m.get(test::black_box(k.as_str())));
+                }
+            });
+        }
+    };
+}
+
+lookup!(map_16, Map<String, usize, 16>, 16);
+lookup!(tagged_16, TaggedMap<String, usize, 16>, 16);
+lookup!(hashbrown_16, hashbrown::HashMap<String, usize>, 16);
+lookup!(map_32, Map<String, usize, 32>, 32);
+lookup!(tagged_32, TaggedMap<String, usize, 32>, 32);
+lookup!(hashbrown_32, hashbrown::HashMap<String, usize>, 32);
+lookup!(map_64, Map<String, usize, 64>, 64);
+lookup!(tagged_64, TaggedMap<String, usize, 64>, 64);
+lookup!(hashbrown_64, hashbrown::HashMap<String, usize>, 64);
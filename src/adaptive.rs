@@ -0,0 +1,140 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::Map;
+use core::borrow::Borrow;
+
+impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
+    /// Get a reference to a single value and move its pair one position
+    /// closer to the front of the map, swapping it with the previous one.
+    ///
+    /// When a few keys are looked up much more often than others, they
+    /// gradually travel to the front of the map, where they are found
+    /// after just a few comparisons. Unlike [`Map::get_and_move_to_front`],
+    /// a key that is looked up only once in a while doesn't jump over
+    /// the frequently used ones. When the lookups are spread evenly over
+    /// the keys, [`Map::get`] is faster, since it doesn't write anything.
+    ///
+    /// Pay attention, this changes the positions of the pairs, so they
+    /// are no longer in the order of their insertion. For example:
+    ///
+    /// ```
+    /// let mut m: micromap::Map<&str, i32, 4> = micromap::Map::new();
+    /// m.insert("one", 1);
+    /// m.insert("two", 2);
+    /// m.insert("three", 3);
+    /// assert_eq!(Some(&3), m.get_and_transpose("three"));
+    /// assert_eq!(vec!["one", "three", "two"], m.keys().copied().collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    pub fn get_and_transpose<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| {
+            if i == 0 {
+                return &self.item(0).1;
+            }
+            self.pairs.swap(i, i - 1);
+            &self.item(i - 1).1
+        })
+    }
+
+    /// Get a reference to a single value and move its pair to the front
+    /// of the map, shifting all pairs before it by one position.
+    ///
+    /// The most recently used keys are always found first. This adapts
+    /// faster than [`Map::get_and_transpose`] when the set of frequently
+    /// used keys changes over time.
+    ///
+    /// Pay attention, this changes the positions of the pairs, so they
+    /// are no longer in the order of their insertion. For example:
+    ///
+    /// ```
+    /// let mut m: micromap::Map<&str, i32, 4> = micromap::Map::new();
+    /// m.insert("one", 1);
+    /// m.insert("two", 2);
+    /// m.insert("three", 3);
+    /// assert_eq!(Some(&3), m.get_and_move_to_front("three"));
+    /// assert_eq!(vec!["three", "one", "two"], m.keys().copied().collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    pub fn get_and_move_to_front<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| {
+            if i > 0 {
+                self.pairs[..=i].rotate_right(1);
+            }
+            &self.item(0).1
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn transposes_found_pair() {
+        let mut m: Map<u8, u8, 4> = (0..4).map(|i| (i, i * 10)).collect();
+        assert_eq!(Some(&30), m.get_and_transpose(&3));
+        assert_eq!(Some(&30), m.get_and_transpose(&3));
+        assert_eq!(vec![0, 3, 1, 2], m.keys().copied().collect::<Vec<_>>());
+        assert_eq!(Some(&30), m.get_and_transpose(&3));
+        assert_eq!(Some(&30), m.get_and_transpose(&3));
+        assert_eq!(vec![3, 0, 1, 2], m.keys().copied().collect::<Vec<_>>());
+        assert_eq!(Some(&30), m.get(&3));
+    }
+
+    #[test]
+    fn moves_found_pair_to_front() {
+        let mut m: Map<u8, u8, 4> = (0..4).map(|i| (i, i * 10)).collect();
+        assert_eq!(Some(&20), m.get_and_move_to_front(&2));
+        assert_eq!(vec![2, 0, 1, 3], m.keys().copied().collect::<Vec<_>>());
+        assert_eq!(Some(&20), m.get_and_move_to_front(&2));
+        assert_eq!(vec![2, 0, 1, 3], m.keys().copied().collect::<Vec<_>>());
+        assert_eq!(Some(&30), m.get_and_move_to_front(&3));
+        assert_eq!(vec![3, 2, 0, 1], m.keys().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn keeps_order_when_not_found() {
+        let mut m: Map<u8, u8, 4> = (0..4).map(|i| (i, i)).collect();
+        assert_eq!(None, m.get_and_transpose(&7));
+        assert_eq!(None, m.get_and_move_to_front(&7));
+        assert_eq!(vec![0, 1, 2, 3], m.keys().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn finds_all_keys_after_reordering() {
+        let mut m: Map<String, usize, 16> = (0..16).map(|i| (format!("k{i}"), i)).collect();
+        for i in [5, 9, 5, 15, 0, 9, 9] {
+            m.get_and_transpose(format!("k{i}").as_str());
+            m.get_and_move_to_front(format!("k{}", 15 - i).as_str());
+        }
+        for i in 0..16 {
+            assert_eq!(Some(&i), m.get(format!("k{i}").as_str()));
+        }
+        assert_eq!(16, m.len());
+    }
+}
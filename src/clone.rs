@@ -20,11 +20,11 @@
 
 use crate::Map;
 
-impl<K: Clone + PartialEq, V: Clone, const N: usize> Clone for Map<K, V, N> {
+impl<K: Clone + PartialEq, V: Clone, const N: usize, E> Clone for Map<K, V, N, E> {
     fn clone(&self) -> Self {
-        let mut m: Self = Self::new();
-        for (k, v) in self.iter() {
-            m.insert(k.clone(), v.clone());
+        let mut m: Self = Self::empty();
+        for (k, v) in self {
+            m.push(k.clone(), v.clone());
         }
         m
     }
@@ -43,6 +43,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::redundant_clone)]
     fn empty_map_can_be_cloned() {
         let m: Map<u8, u8, 0> = Map::new();
         assert!(m.clone().is_empty());
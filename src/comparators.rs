@@ -0,0 +1,146 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{ByBits, CaseInsensitive, DefaultEq, KeyEq};
+
+impl<Q: PartialEq + ?Sized> KeyEq<Q> for DefaultEq {
+    const NATIVE: bool = true;
+
+    #[inline]
+    fn eq(a: &Q, b: &Q) -> bool {
+        a == b
+    }
+}
+
+impl<Q: AsRef<[u8]> + ?Sized> KeyEq<Q> for CaseInsensitive {
+    #[inline]
+    fn eq(a: &Q, b: &Q) -> bool {
+        a.as_ref().eq_ignore_ascii_case(b.as_ref())
+    }
+}
+
+impl KeyEq<f32> for ByBits {
+    #[inline]
+    fn eq(a: &f32, b: &f32) -> bool {
+        a.to_bits() == b.to_bits()
+    }
+}
+
+impl KeyEq<f64> for ByBits {
+    #[inline]
+    fn eq(a: &f64, b: &f64) -> bool {
+        a.to_bits() == b.to_bits()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::Map;
+
+    #[test]
+    fn compares_ignoring_case() {
+        assert!(<CaseInsensitive as KeyEq<str>>::eq("Host", "HOST"));
+        assert!(!<CaseInsensitive as KeyEq<str>>::eq("Host", "Hast"));
+        assert!(<CaseInsensitive as KeyEq<[u8]>>::eq(b"a-B", b"A-b"));
+    }
+
+    #[test]
+    fn finds_headers_in_any_case() {
+        let mut m: Map<String, u8, 4, CaseInsensitive> = Map::with_key_eq(CaseInsensitive);
+        m.insert("Content-Length".to_string(), 1);
+        assert_eq!(Some(1), m.insert("content-length".to_string(), 2));
+        assert_eq!(1, m.len());
+        assert!(m.contains_key("CONTENT-LENGTH"));
+        assert_eq!(
+            Some((&"Content-Length".to_string(), &2)),
+            m.get_key_value("Content-length")
+        );
+        *m.get_mut("content-LENGTH").unwrap() += 1;
+        assert_eq!(Some(3), m.remove("CONTENT-length"));
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn finds_floats_by_bits() {
+        let mut m: Map<f64, &str, 4, ByBits> = Map::default();
+        m.insert(f64::NAN, "nan");
+        m.insert(0.0, "zero");
+        m.insert(-0.0, "negative zero");
+        assert_eq!(3, m.len());
+        assert_eq!(Some(&"nan"), m.get(&f64::NAN));
+        assert_eq!(Some(&"negative zero"), m.get(&-0.0));
+        assert_eq!(
+            Some((f64::NAN.to_bits(), "nan")),
+            m.remove_entry(&f64::NAN).map(|(k, v)| (k.to_bits(), v))
+        );
+        let plain: Map<f64, &str, 4> = Map::from([(f64::NAN, "nan")]);
+        assert_eq!(None, plain.get(&f64::NAN));
+    }
+
+    #[test]
+    fn iterates_with_custom_eq() {
+        let mut m: Map<&str, u8, 4, CaseInsensitive> = Map::with_key_eq(CaseInsensitive);
+        m.insert("a", 1);
+        m.insert("B", 2);
+        m.insert("b", 3);
+        assert_eq!(vec![(&"a", &1), (&"B", &3)], m.iter().collect::<Vec<_>>());
+        assert_eq!(vec![&"a", &"B"], m.keys().collect::<Vec<_>>());
+        assert_eq!(1, m.retain(|_, v| *v > 1));
+        let c = m.clone();
+        assert_eq!(r#"{"B": 3}"#, format!("{c:?}"));
+        assert_eq!(vec![("B", 3)], m.drain().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn extends_and_indexes_with_custom_eq() {
+        let mut m: Map<&str, u8, 2, CaseInsensitive> = [("x", 1), ("X", 2)].into_iter().collect();
+        m.extend([("y", 3), ("Y", 4)]);
+        assert_eq!(2, m.len());
+        m["x"] += 10;
+        assert_eq!(12, m["X"]);
+        assert_eq!(4, m["y"]);
+    }
+
+    #[test]
+    fn makes_entries_with_custom_eq() {
+        let mut m: Map<&str, u8, 2, CaseInsensitive> = Map::with_key_eq(CaseInsensitive);
+        for w in ["Accept", "ACCEPT", "host", "accept"] {
+            *m.entry(w).or_insert(0) += 1;
+        }
+        assert_eq!(2, m.len());
+        assert_eq!(Some(&3), m.get("accept"));
+        assert_eq!(Some(&1), m.get("Host"));
+    }
+
+    #[test]
+    fn compares_maps_with_custom_eq() {
+        let mut a: Map<&str, u8, 2, CaseInsensitive> = Map::with_key_eq(CaseInsensitive);
+        a.insert("Host", 1);
+        a.insert("Accept", 2);
+        let mut b: Map<&str, u8, 2, CaseInsensitive> = Map::with_key_eq(CaseInsensitive);
+        b.insert("accept", 2);
+        b.insert("HOST", 1);
+        assert_eq!(a, b);
+        b.insert("host", 3);
+        assert_ne!(a, b);
+    }
+}
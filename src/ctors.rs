@@ -18,15 +18,39 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Map;
-use core::mem::MaybeUninit;
+use crate::{Map, SortedMap};
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ptr;
 
-impl<K: PartialEq, V, const N: usize> Default for Map<K, V, N> {
+impl<K: PartialEq, V, const N: usize, E> Default for Map<K, V, N, E> {
     /// Make a default empty [`Map`].
     #[inline]
-    #[must_use]
     fn default() -> Self {
-        Self::new()
+        Self::empty()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize, E> Map<K, V, N, E> {
+    /// Make it, comparing the keys with the given [`KeyEq`](crate::KeyEq).
+    #[inline]
+    #[must_use]
+    pub const fn with_key_eq(eq: E) -> Self {
+        mem::forget(eq);
+        Self::empty()
+    }
+
+    /// Internal function to make an empty map.
+    #[inline]
+    #[allow(clippy::uninit_assumed_init)]
+    pub(crate) const fn empty() -> Self {
+        unsafe {
+            Self {
+                len: 0,
+                pairs: MaybeUninit::<[MaybeUninit<(K, V)>; N]>::uninit().assume_init(),
+                eq: PhantomData,
+            }
+        }
     }
 }
 
@@ -37,24 +61,67 @@ impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
     /// this is how you make a map of four key-values pairs:
     #[inline]
     #[must_use]
-    #[allow(clippy::uninit_assumed_init)]
     pub const fn new() -> Self {
-        unsafe {
-            Self {
-                next: 0,
-                pairs: MaybeUninit::<[MaybeUninit<Option<(K, V)>>; N]>::uninit().assume_init(),
-            }
+        Self::empty()
+    }
+
+    /// Make it from an array of pairs, which may be shorter than the map.
+    ///
+    /// This can be used in `const` and `static` contexts, which is what
+    /// the [`micromap!`](crate::micromap) macro does. If the array is
+    /// longer than the map, the program doesn't compile. The keys are not
+    /// compared with each other, since it's impossible in a `const` function.
+    /// Thus, all keys in the array must be different. If they are not, this
+    /// produces an invalid map: the behaviour of [`Map::len`], [`Map::remove`],
+    /// `==` and the other methods is unspecified. Use [`Map::from`] outside
+    /// of `const` contexts, which keeps the last value of a duplicate key.
+    ///
+    /// ```
+    /// use micromap::Map;
+    /// static PORTS: Map<&str, u16, 4> = Map::from_array([("http", 80), ("https", 443)]);
+    /// assert_eq!(Some(&443), PORTS.get("https"));
+    /// ```
+    ///
+    /// An array longer than the map doesn't compile:
+    ///
+    /// ```compile_fail
+    /// let _: micromap::Map<u8, u8, 1> = micromap::Map::from_array([(1, 1), (2, 2)]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn from_array<const M: usize>(pairs: [(K, V); M]) -> Self {
+        const { assert!(M <= N, "The array is longer than the capacity of the map") };
+        let pairs = ManuallyDrop::new(pairs);
+        let src = ptr::from_ref(&pairs).cast::<(K, V)>();
+        let mut m = Self::new();
+        while m.len < M {
+            m.pairs[m.len] = MaybeUninit::new(unsafe { ptr::read(src.add(m.len)) });
+            m.len += 1;
         }
+        m
     }
 }
 
-impl<K: PartialEq, V, const N: usize> Drop for Map<K, V, N> {
+impl<K: Ord, V, const N: usize> Default for SortedMap<K, V, N> {
+    /// Make a default empty [`SortedMap`].
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V, const N: usize> SortedMap<K, V, N> {
+    /// Make it.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { map: Map::new() }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize, E> Drop for Map<K, V, N, E> {
     fn drop(&mut self) {
-        for i in 0..self.next {
-            unsafe {
-                self.pairs[i].assume_init_drop();
-            }
-        }
+        self.clear();
     }
 }
 
@@ -75,6 +142,31 @@ mod test {
         assert_eq!(0, m.len());
     }
 
+    #[test]
+    fn makes_default_sorted_map() {
+        let m: SortedMap<u8, u8, 8> = SortedMap::default();
+        assert_eq!(0, m.len());
+    }
+
+    #[test]
+    fn makes_map_from_shorter_array() {
+        const M: Map<u8, u8, 8> = Map::from_array([(1, 10), (2, 20)]);
+        assert_eq!(2, M.len());
+        assert_eq!(Some(&20), M.get(&2));
+        let empty: Map<u8, u8, 2> = Map::from_array([]);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn moves_values_from_array() {
+        use std::rc::Rc;
+        let v = Rc::new(());
+        let m: Map<u8, Rc<()>, 3> = Map::from_array([(1, Rc::clone(&v)), (2, Rc::clone(&v))]);
+        assert_eq!(3, Rc::strong_count(&v));
+        drop(m);
+        assert_eq!(1, Rc::strong_count(&v));
+    }
+
     #[test]
     fn drops_correctly() {
         let _m: Map<Vec<u8>, u8, 8> = Map::new();
@@ -18,23 +18,51 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Map;
+use crate::{Map, SortedMap};
 use core::fmt;
 use core::fmt::{Debug, Display, Formatter};
 
-impl<K: PartialEq + Display, V: Display, const N: usize> Display for Map<K, V, N> {
+impl<K: PartialEq + Display, V: Display, const N: usize, E> Display for Map<K, V, N, E> {
+    /// Print the pairs in the order of their positions, like `{one: 1, two: 2}`.
+    ///
+    /// Keys and values are printed with their [`Display`], without quotes,
+    /// and the formatting options, like precision, are applied to each of them:
+    ///
+    /// ```
+    /// let m: micromap::Map<&str, f64, 4> = micromap::Map::from([("pi", 3.14159)]);
+    /// assert_eq!("{pi: 3.14}", format!("{m:.2}"));
+    /// ```
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        <&Self as Debug>::fmt(&self, f)
+        f.write_str("{")?;
+        for (i, (k, v)) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            Display::fmt(k, f)?;
+            f.write_str(": ")?;
+            Display::fmt(v, f)?;
+        }
+        f.write_str("}")
     }
 }
 
-impl<K: PartialEq + Display, V: Display, const N: usize> Debug for Map<K, V, N> {
+impl<K: PartialEq + Debug, V: Debug, const N: usize, E> Debug for Map<K, V, N, E> {
+    /// Print the pairs in the order of their positions, just like
+    /// the `HashMap` does, including the pretty form of `{:#?}`.
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let mut parts = vec![];
-        for (k, v) in self.iter() {
-            parts.push(std::format!("{k}: {v}"));
-        }
-        f.write_str(std::format!("{{{}}}", parts.join(", ").as_str()).as_str())
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord + Display, V: Display, const N: usize> Display for SortedMap<K, V, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.map, f)
+    }
+}
+
+impl<K: Ord + Debug, V: Debug, const N: usize> Debug for SortedMap<K, V, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.map, f)
     }
 }
 
@@ -48,7 +76,32 @@ mod test {
         let mut m: Map<&str, i32, 10> = Map::new();
         m.insert("one", 42);
         m.insert("two", 16);
-        assert_eq!("{one: 42, two: 16}", format!("{:?}", m));
+        assert_eq!(r#"{"one": 42, "two": 16}"#, format!("{m:?}"));
+    }
+
+    #[test]
+    fn debugs_map_pretty() {
+        let mut m: Map<String, Vec<u8>, 4> = Map::new();
+        m.insert("one".to_string(), vec![1]);
+        assert_eq!(
+            "{\n    \"one\": [\n        1,\n    ],\n}",
+            format!("{m:#?}")
+        );
+        let empty: Map<u8, u8, 1> = Map::new();
+        assert_eq!("{}", format!("{empty:?}"));
+    }
+
+    #[test]
+    fn debugs_inside_derived() {
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Config {
+            flags: Map<&'static str, bool, 2>,
+        }
+        let c = Config {
+            flags: Map::from([("verbose", true)]),
+        };
+        assert_eq!(r#"Config { flags: {"verbose": true} }"#, format!("{c:?}"));
     }
 
     #[test]
@@ -56,6 +109,22 @@ mod test {
         let mut m: Map<&str, i32, 10> = Map::new();
         m.insert("one", 42);
         m.insert("two", 16);
-        assert_eq!("{one: 42, two: 16}", format!("{}", m));
+        assert_eq!("{one: 42, two: 16}", format!("{m}"));
+        assert_eq!("{}", format!("{}", Map::<u8, u8, 1>::new()));
+    }
+
+    #[test]
+    fn displays_with_options() {
+        let m: Map<u8, f64, 2> = Map::from([(1, 0.5)]);
+        assert_eq!("{  1: 0.5}", format!("{m:>3}"));
+    }
+
+    #[test]
+    fn debugs_sorted_map() {
+        let mut m: SortedMap<&str, i32, 10> = SortedMap::new();
+        m.insert("two", 16);
+        m.insert("one", 42);
+        assert_eq!(r#"{"one": 42, "two": 16}"#, format!("{m:?}"));
+        assert_eq!("{one: 42, two: 16}", format!("{m}"));
     }
 }
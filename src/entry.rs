@@ -0,0 +1,404 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::error::OVERFLOW;
+use crate::{Entry, KeyEq, Map, OccupiedEntry, VacantEntry};
+use core::mem;
+
+impl<K: PartialEq, V, const N: usize, E: KeyEq<K>> Map<K, V, N, E> {
+    /// Get the given key's corresponding entry in the map for in-place
+    /// manipulation.
+    ///
+    /// The array is scanned only once: the position of the key is remembered
+    /// in the entry. For example:
+    ///
+    /// ```
+    /// let mut m: micromap::Map<&str, u32, 4> = micromap::Map::new();
+    /// for w in ["a", "b", "a"] {
+    ///     *m.entry(w).or_insert(0) += 1;
+    /// }
+    /// assert_eq!(2, m["a"]);
+    /// assert_eq!(1, m["b"]);
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, k: K) -> Entry<'_, K, V, N, E> {
+        match self.position(&k) {
+            Some(index) => Entry::Occupied(OccupiedEntry { index, map: self }),
+            None => Entry::Vacant(VacantEntry { key: k, map: self }),
+        }
+    }
+}
+
+impl<'a, K: PartialEq, V, const N: usize, E> Entry<'a, K, V, N, E> {
+    /// Ensures a value is in the entry by inserting the default if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Panics
+    ///
+    /// If the entry is vacant and there is no more space in the map.
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => e.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function if empty, and returns a mutable reference to the value in the entry.
+    ///
+    /// # Panics
+    ///
+    /// If the entry is vacant and there is no more space in the map.
+    #[inline]
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => e.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function, which gets the key, if empty, and returns a mutable reference
+    /// to the value in the entry.
+    ///
+    /// # Panics
+    ///
+    /// If the entry is vacant and there is no more space in the map.
+    #[inline]
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => {
+                let v = default(&e.key);
+                e.insert(v)
+            }
+        }
+    }
+
+    /// Returns a reference to this entry's key.
+    #[inline]
+    #[must_use]
+    pub const fn key(&self) -> &K {
+        match self {
+            Self::Occupied(e) => e.key(),
+            Self::Vacant(e) => e.key(),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    #[inline]
+    #[must_use]
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Self::Occupied(mut e) => {
+                f(e.get_mut());
+                Self::Occupied(e)
+            }
+            Self::Vacant(e) => Self::Vacant(e),
+        }
+    }
+
+    /// Sets the value of the entry, and returns an [`OccupiedEntry`].
+    ///
+    /// # Panics
+    ///
+    /// If the entry is vacant and there is no more space in the map.
+    #[inline]
+    pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V, N, E> {
+        match self {
+            Self::Occupied(mut e) => {
+                e.insert(value);
+                e
+            }
+            Self::Vacant(e) => e.insert_entry(value),
+        }
+    }
+}
+
+impl<'a, K: PartialEq, V: Default, const N: usize, E> Entry<'a, K, V, N, E> {
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Panics
+    ///
+    /// If the entry is vacant and there is no more space in the map.
+    #[inline]
+    pub fn or_default(self) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => e.insert(V::default()),
+        }
+    }
+}
+
+impl<'a, K: PartialEq, V, const N: usize, E> OccupiedEntry<'a, K, V, N, E> {
+    /// Gets the position of the pair in the map.
+    #[inline]
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Gets a reference to the key in the entry.
+    #[inline]
+    #[must_use]
+    pub const fn key(&self) -> &K {
+        &self.pair().0
+    }
+
+    /// Gets a reference to the value in the entry.
+    #[inline]
+    #[must_use]
+    pub const fn get(&self) -> &V {
+        &self.pair().1
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    ///
+    /// If you need a reference which may outlive the destruction of the
+    /// [`Entry`] value, see [`OccupiedEntry::into_mut`].
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut V {
+        &mut self.pair_mut().1
+    }
+
+    /// Converts the entry into a mutable reference to the value in the entry
+    /// with a lifetime bound to the map itself.
+    #[inline]
+    #[must_use]
+    pub const fn into_mut(self) -> &'a mut V {
+        &mut self.map.item_mut(self.index).1
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    #[inline]
+    pub const fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Takes the value out of the entry, and returns it.
+    #[inline]
+    #[must_use]
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Take the ownership of the key and value from the map.
+    ///
+    /// The last pair in the map takes the place of the removed one.
+    #[inline]
+    #[must_use]
+    pub const fn remove_entry(self) -> (K, V) {
+        self.map.swap_take(self.index)
+    }
+
+    /// Internal function to get access to the pair of this entry.
+    #[inline]
+    const fn pair(&self) -> &(K, V) {
+        self.map.item(self.index)
+    }
+
+    /// Internal function to get mutable access to the pair of this entry.
+    #[inline]
+    const fn pair_mut(&mut self) -> &mut (K, V) {
+        self.map.item_mut(self.index)
+    }
+}
+
+impl<'a, K: PartialEq, V, const N: usize, E> VacantEntry<'a, K, V, N, E> {
+    /// Gets the position, which the new pair will have in the map.
+    #[inline]
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        self.map.len
+    }
+
+    /// Gets a reference to the key that would be used when inserting a value
+    /// through the [`VacantEntry`].
+    #[inline]
+    #[must_use]
+    pub const fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Take ownership of the key.
+    #[inline]
+    #[must_use]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Sets the value of the entry with the [`VacantEntry`]'s key,
+    /// and returns a mutable reference to it.
+    ///
+    /// # Panics
+    ///
+    /// If there is no more space in the map.
+    #[inline]
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.insert_entry(value).into_mut()
+    }
+
+    /// Sets the value of the entry with the [`VacantEntry`]'s key,
+    /// and returns an [`OccupiedEntry`].
+    ///
+    /// # Panics
+    ///
+    /// If there is no more space in the map.
+    #[inline]
+    pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V, N, E> {
+        assert!(self.map.len < N, "{OVERFLOW}");
+        self.map.push(self.key, value);
+        OccupiedEntry {
+            index: self.map.len - 1,
+            map: self.map,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn inserts_into_vacant_entry() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        *m.entry("one").or_insert(40) += 2;
+        assert_eq!(42, m["one"]);
+        assert_eq!(1, m.len());
+    }
+
+    #[test]
+    fn updates_occupied_entry() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        m.insert("one", 1);
+        *m.entry("one").or_insert(40) += 2;
+        assert_eq!(3, m["one"]);
+        assert_eq!(1, m.len());
+    }
+
+    #[test]
+    fn inserts_with_closures() {
+        let mut m: Map<u8, u8, 10> = Map::new();
+        m.entry(1).or_insert_with(|| 2);
+        m.entry(3).or_insert_with_key(|k| k * 2);
+        assert_eq!(2, m[&1]);
+        assert_eq!(6, m[&3]);
+    }
+
+    #[test]
+    fn inserts_default() {
+        let mut m: Map<u8, Vec<u8>, 10> = Map::new();
+        m.entry(1).or_default().push(42);
+        m.entry(1).or_default().push(16);
+        assert_eq!(vec![42, 16], m[&1]);
+    }
+
+    #[test]
+    fn modifies_only_occupied() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        m.entry("one").and_modify(|v| *v += 1).or_insert(42);
+        assert_eq!(42, m["one"]);
+        m.entry("one").and_modify(|v| *v += 1).or_insert(42);
+        assert_eq!(43, m["one"]);
+    }
+
+    #[test]
+    fn returns_entry_key() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        assert_eq!(&"one", m.entry("one").key());
+        m.insert("one", 1);
+        assert_eq!(&"one", m.entry("one").key());
+    }
+
+    #[test]
+    fn removes_occupied_entry() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        m.insert("one", 42);
+        m.insert("two", 16);
+        match m.entry("one") {
+            Entry::Occupied(e) => assert_eq!(("one", 42), e.remove_entry()),
+            Entry::Vacant(_) => panic!("The entry must be occupied"),
+        }
+        assert_eq!(1, m.len());
+        assert!(!m.contains_key(&"one"));
+    }
+
+    #[test]
+    fn replaces_occupied_value() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        m.insert("one", 42);
+        if let Entry::Occupied(mut e) = m.entry("one") {
+            assert_eq!(42, e.insert(16));
+            assert_eq!(&16, e.get());
+        }
+        assert_eq!(16, m["one"]);
+    }
+
+    #[test]
+    fn inserts_entry() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        let e = m.entry("one").insert_entry(42);
+        assert_eq!(&"one", e.key());
+        assert_eq!(42, e.remove());
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn knows_entry_positions() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        m.insert("one", 1);
+        m.insert("two", 2);
+        assert_eq!(1, m.entry("two").insert_entry(3).index());
+        assert_eq!(2, m.entry("three").insert_entry(3).index());
+    }
+
+    #[test]
+    fn reuses_free_slot() {
+        let mut m: Map<u8, u8, 2> = Map::new();
+        m.insert(1, 1);
+        m.insert(2, 2);
+        m.remove(&1);
+        m.entry(3).or_insert(3);
+        assert_eq!(2, m.len());
+        assert_eq!(3, m[&3]);
+    }
+
+    #[test]
+    fn takes_key_back_from_vacant() {
+        let mut m: Map<String, u8, 2> = Map::new();
+        if let Entry::Vacant(e) = m.entry("one".to_string()) {
+            assert_eq!("one", e.into_key());
+        }
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "No more keys available in the map")]
+    fn reports_overflow_in_vacant_entry() {
+        let mut m: Map<u8, u8, 1> = Map::new();
+        m.insert(1, 1);
+        m.entry(2).or_insert(2);
+    }
+}
@@ -18,9 +18,9 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Map;
+use crate::{KeyEq, Map};
 
-impl<K: PartialEq, V: PartialEq, const N: usize> PartialEq for Map<K, V, N> {
+impl<K: PartialEq, V: PartialEq, const N: usize, E: KeyEq<K>> PartialEq for Map<K, V, N, E> {
     /// Two maps can be compared.
     ///
     /// For example:
@@ -42,11 +42,11 @@ impl<K: PartialEq, V: PartialEq, const N: usize> PartialEq for Map<K, V, N> {
     /// ```
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        return self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v));
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
     }
 }
 
-impl<K: Eq, V: Eq, const N: usize> Eq for Map<K, V, N> {}
+impl<K: Eq, V: Eq, const N: usize, E: KeyEq<K>> Eq for Map<K, V, N, E> {}
 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,113 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::CapacityError;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+
+/// The message, which is shared by the panics and the errors.
+pub const OVERFLOW: &str = "No more keys available in the map";
+
+impl<K, V> CapacityError<K, V> {
+    /// Make it, from the pair that was rejected.
+    #[inline]
+    #[must_use]
+    pub const fn new(k: K, v: V) -> Self {
+        Self {
+            pair: (k, v),
+            accepted: 0,
+        }
+    }
+
+    /// Set the number of pairs accepted before the rejected one.
+    #[inline]
+    #[must_use]
+    pub(crate) const fn after(mut self, accepted: usize) -> Self {
+        self.accepted = accepted;
+        self
+    }
+
+    /// How many pairs were accepted before the rejected one, by the operation
+    /// that inserted many pairs at once, like [`Map::try_extend`](crate::Map::try_extend).
+    /// It is zero when a single pair was inserted.
+    #[inline]
+    #[must_use]
+    pub const fn accepted(&self) -> usize {
+        self.accepted
+    }
+
+    /// The key that was rejected.
+    #[inline]
+    #[must_use]
+    pub const fn key(&self) -> &K {
+        &self.pair.0
+    }
+
+    /// The value that was rejected.
+    #[inline]
+    #[must_use]
+    pub const fn value(&self) -> &V {
+        &self.pair.1
+    }
+
+    /// Take the rejected pair back.
+    #[inline]
+    #[must_use]
+    pub fn into_pair(self) -> (K, V) {
+        self.pair
+    }
+}
+
+impl<K, V> Display for CapacityError<K, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(OVERFLOW)
+    }
+}
+
+impl<K, V> Debug for CapacityError<K, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "CapacityError: {OVERFLOW}")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V> std::error::Error for CapacityError<K, V> {}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn returns_pair_back() {
+        let e = CapacityError::new("one", 42);
+        assert_eq!(&"one", e.key());
+        assert_eq!(&42, e.value());
+        assert_eq!(0, e.accepted());
+        assert_eq!(("one", 42), e.into_pair());
+    }
+
+    #[test]
+    fn prints_error() {
+        let e = CapacityError::new(1, 2);
+        assert_eq!(OVERFLOW, format!("{e}"));
+        assert_eq!(format!("CapacityError: {OVERFLOW}"), format!("{e:?}"));
+    }
+}
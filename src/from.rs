@@ -18,28 +18,165 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Map;
+use crate::{CapacityError, KeyEq, Map};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
-impl<K: PartialEq, V, const N: usize> FromIterator<(K, V)> for Map<K, V, N> {
+impl<K: PartialEq, V, const N: usize, E: KeyEq<K>> Map<K, V, N, E> {
+    /// Try to make a map from an iterator of pairs.
+    ///
+    /// Duplicate keys are allowed: the last value wins.
+    ///
+    /// ```
+    /// use micromap::Map;
+    /// let e = Map::<u8, u8, 2>::try_from_iter([(1, 1), (2, 2), (3, 3), (4, 4)]).err().unwrap();
+    /// assert_eq!(2, e.accepted());
+    /// assert_eq!((3, 3), e.into_pair());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If there are more different keys than the capacity of the map,
+    /// a [`CapacityError`] is returned, with the first pair that didn't fit
+    /// and the number of pairs taken from the iterator before it. The rest
+    /// of the iterator is not consumed.
+    #[inline]
+    pub fn try_from_iter<I: IntoIterator<Item = (K, V)>>(
+        iter: I,
+    ) -> Result<Self, CapacityError<K, V>> {
+        let mut m = Self::empty();
+        m.try_extend(iter)?;
+        Ok(m)
+    }
+
+    /// Try to insert all pairs from an iterator into the map.
+    ///
+    /// Values of the keys already in the map are updated.
+    ///
+    /// ```
+    /// use micromap::Map;
+    /// let mut m: Map<u8, u8, 3> = Map::new();
+    /// m.insert(1, 1);
+    /// let e = m.try_extend([(1, 10), (2, 2), (3, 3), (4, 4)]).unwrap_err();
+    /// assert_eq!(3, e.accepted());
+    /// assert_eq!(&4, e.key());
+    /// assert_eq!(3, m.len());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If there is no space for a new key, a [`CapacityError`] is returned,
+    /// with the first pair that didn't fit and the number of pairs taken from
+    /// the iterator before it. Those pairs stay in the map, while the rest
+    /// of the iterator is not consumed.
+    #[inline]
+    pub fn try_extend<I: IntoIterator<Item = (K, V)>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), CapacityError<K, V>> {
+        for (i, (k, v)) in iter.into_iter().enumerate() {
+            self.try_insert(k, v).map_err(|e| e.after(i))?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: PartialEq, V, const N: usize, E: KeyEq<K>> FromIterator<(K, V)> for Map<K, V, N, E> {
+    /// Make a map from an iterator of pairs.
+    ///
+    /// # Panics
+    ///
+    /// If there are more different keys than the capacity of the map,
+    /// in both "debug" and "release" modes. If you want to handle the overflow,
+    /// use [`Map::try_from_iter`] instead.
     #[inline]
-    #[must_use]
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
-        let mut m: Self = Self::new();
+        let mut m: Self = Self::empty();
+        m.extend(iter);
+        m
+    }
+}
+
+impl<K: PartialEq, V, const N: usize, E: KeyEq<K>> Extend<(K, V)> for Map<K, V, N, E> {
+    /// Insert all pairs, updating the values of the keys already in the map.
+    ///
+    /// # Panics
+    ///
+    /// If there is no space for a new key, in both "debug" and "release"
+    /// modes. The pairs inserted before it stay in the map. If you want
+    /// to handle the overflow, use [`Map::try_extend`] instead.
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
         for (k, v) in iter {
-            m.insert(k, v);
+            self.insert(k, v);
         }
-        m
     }
 }
 
-impl<K: PartialEq, V, const N: usize> From<[(K, V); N]> for Map<K, V, N> {
+impl<'a, K: PartialEq + Copy + 'a, V: Copy + 'a, const N: usize, E: KeyEq<K>> Extend<(&'a K, &'a V)>
+    for Map<K, V, N, E>
+{
+    /// Insert copies of all pairs, like the [`Extend`] of owned pairs does.
+    ///
+    /// # Panics
+    ///
+    /// If there is no space for a new key.
+    #[inline]
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(k, v)| (*k, *v)));
+    }
+}
+
+impl<K: PartialEq, V, const M: usize, const N: usize> From<[(K, V); M]> for Map<K, V, N> {
+    /// Make a map from an array, which may be shorter than the map, leaving
+    /// space for more pairs.
+    ///
+    /// If the array is longer than the map, the program doesn't compile.
+    /// Duplicate keys are allowed: the last value wins.
+    ///
+    /// ```compile_fail
+    /// let _: micromap::Map<u8, u8, 1> = micromap::Map::from([(1, 1), (2, 2)]);
+    /// ```
     #[inline]
-    #[must_use]
-    fn from(arr: [(K, V); N]) -> Self {
+    fn from(arr: [(K, V); M]) -> Self {
+        const { assert!(M <= N, "The array is longer than the capacity of the map") };
         Self::from_iter(arr)
     }
 }
 
+impl<K: PartialEq + Clone, V: Clone, const N: usize> TryFrom<&[(K, V)]> for Map<K, V, N> {
+    type Error = CapacityError<K, V>;
+
+    /// Make a map from a slice of pairs, cloning them.
+    ///
+    /// # Errors
+    ///
+    /// If there are more different keys in the slice than the capacity of
+    /// the map, the first pair that doesn't fit is returned in the error,
+    /// together with the number of pairs before it.
+    #[inline]
+    fn try_from(pairs: &[(K, V)]) -> Result<Self, Self::Error> {
+        Self::try_from_iter(pairs.iter().cloned())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: PartialEq, V, const N: usize> TryFrom<Vec<(K, V)>> for Map<K, V, N> {
+    type Error = CapacityError<K, V>;
+
+    /// Make a map from a vector of pairs, moving them.
+    ///
+    /// # Errors
+    ///
+    /// If there are more different keys in the vector than the capacity of
+    /// the map, the first pair that doesn't fit is returned in the error,
+    /// together with the number of pairs before it.
+    #[inline]
+    fn try_from(pairs: Vec<(K, V)>) -> Result<Self, Self::Error> {
+        Self::try_from_iter(pairs)
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -56,19 +193,89 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
-    #[cfg(debug_assertions)]
+    #[should_panic(expected = "No more keys available in the map")]
     fn from_larger_iter() {
         let vec = Vec::from(TEST_ARRAY);
         let _m: Map<i32, &str, 1> = Map::from_iter(vec);
     }
 
+    #[test]
+    fn try_from_iter() {
+        let m: Map<i32, &str, 5> = Map::try_from_iter(TEST_ARRAY).unwrap();
+        assert_eq!(m.len(), 5);
+    }
+
+    #[test]
+    fn try_from_larger_iter() {
+        let e = Map::<i32, &str, 2>::try_from_iter(TEST_ARRAY)
+            .err()
+            .unwrap();
+        assert_eq!(e.accepted(), 2);
+        assert_eq!(e.into_pair(), (3, "tue"));
+    }
+
+    #[test]
+    fn counts_duplicates_as_accepted() {
+        let e = Map::<i32, i32, 1>::try_from_iter([(1, 1), (1, 2), (2, 2)])
+            .err()
+            .unwrap();
+        assert_eq!(e.accepted(), 2);
+        assert_eq!(e.key(), &2);
+    }
+
+    #[test]
+    fn try_extend_keeps_accepted() {
+        let mut m: Map<i32, &str, 3> = Map::new();
+        m.insert(5, "fri");
+        let mut iter = TEST_ARRAY.into_iter();
+        let e = m.try_extend(iter.by_ref()).err().unwrap();
+        assert_eq!(e.accepted(), 2);
+        assert_eq!(e.key(), &3);
+        assert_eq!(m.len(), 3);
+        assert_eq!(m[&5], "fri");
+        assert_eq!(iter.next(), Some((4, "wed")));
+    }
+
+    #[test]
+    fn extends_map() {
+        let mut m: Map<i32, &str, 6> = Map::new();
+        m.insert(1, "one");
+        m.extend(TEST_ARRAY);
+        assert_eq!(m.len(), 5);
+        assert_eq!(m[&1], "sun");
+    }
+
+    #[test]
+    fn extends_from_refs() {
+        let other: Map<i32, i32, 3> = Map::from([(1, 10), (2, 20)]);
+        let mut m: Map<i32, i32, 3> = Map::from([(3, 30)]);
+        m.extend(&other);
+        assert_eq!(m.len(), 3);
+        assert_eq!(m[&2], 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "No more keys available in the map")]
+    fn extends_too_much() {
+        let mut m: Map<i32, &str, 4> = Map::new();
+        m.extend(TEST_ARRAY);
+    }
+
     #[test]
     fn from_array() {
-        let m = Map::from(TEST_ARRAY);
+        let m: Map<_, _, 5> = Map::from(TEST_ARRAY);
         assert_eq!(m.len(), 5);
     }
 
+    #[test]
+    fn from_shorter_array() {
+        let mut m: Map<i32, i32, 16> = [(1, 2)].into();
+        assert_eq!(m.len(), 1);
+        assert_eq!(m.capacity(), 16);
+        m.insert(3, 4);
+        assert_eq!(m.len(), 2);
+    }
+
     #[test]
     fn array_into_map() {
         let m: Map<i32, &str, 5> = TEST_ARRAY.into();
@@ -78,8 +285,43 @@ mod test {
     #[test]
     fn from_with_duplicates() {
         let arr = [(1, "sun"), (2, "mon"), (3, "tue"), (1, "wed"), (2, "thu")];
-        let m = Map::from(arr);
+        let m: Map<_, _, 5> = Map::from(arr);
         assert_eq!(m.len(), 3);
         assert_eq!(m[&2], "thu");
     }
+
+    #[test]
+    fn try_from_slice() {
+        let m: Map<i32, &str, 5> = Map::try_from(&TEST_ARRAY[..]).unwrap();
+        assert_eq!(m.len(), 5);
+        assert_eq!(m[&3], "tue");
+    }
+
+    #[test]
+    fn try_from_larger_slice() {
+        let e = Map::<i32, &str, 3>::try_from(&TEST_ARRAY[..])
+            .err()
+            .unwrap();
+        assert_eq!(3, e.accepted());
+        assert_eq!((4, "wed"), e.into_pair());
+    }
+
+    #[test]
+    fn try_from_slice_with_duplicates() {
+        let pairs = [(1, 1), (1, 2), (1, 3)];
+        let m: Map<i32, i32, 1> = pairs.as_slice().try_into().unwrap();
+        assert_eq!(m[&1], 3);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_from_vec() {
+        let m: Map<i32, &str, 8> = Vec::from(TEST_ARRAY).try_into().unwrap();
+        assert_eq!(m.len(), 5);
+        let e = Map::<i32, &str, 4>::try_from(Vec::from(TEST_ARRAY))
+            .err()
+            .unwrap();
+        assert_eq!(4, e.accepted());
+        assert_eq!(&5, e.key());
+    }
 }
@@ -0,0 +1,149 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::tagged::Fx;
+use crate::Map;
+use core::hash::{Hash, Hasher};
+
+impl<K: PartialEq + Hash, V: Hash, const N: usize> Hash for Map<K, V, N> {
+    /// Hash the map, regardless of the order of its pairs.
+    ///
+    /// Just like the equality, the hash doesn't depend on the order of
+    /// the pairs, thus equal maps always have equal hashes. Each pair is
+    /// hashed on its own and the hashes are summed up, which is then fed into
+    /// the hasher, together with the length of the map.
+    ///
+    /// The pairs can't be fed into the given hasher one by one, since the
+    /// sum must not depend on their order. Instead, they are hashed by a fast
+    /// internal hasher, seeded by what the given hasher has seen so far. Thus,
+    /// a keyed hasher, like the default one of `HashMap`, keeps the hashes
+    /// of the pairs unpredictable, but the internal hasher is not designed to
+    /// resist collisions crafted on purpose, the way `SipHash` is. For example:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// let mut a: micromap::Map<u8, &str, 4> = micromap::Map::new();
+    /// a.insert(1, "one");
+    /// a.insert(2, "two");
+    /// let mut b: micromap::Map<u8, &str, 4> = micromap::Map::new();
+    /// b.insert(2, "two");
+    /// b.insert(1, "one");
+    /// let set: HashSet<_> = [a, b].into_iter().collect();
+    /// assert_eq!(1, set.len());
+    /// ```
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        let seed = state.finish();
+        let sum = self
+            .iter()
+            .fold(0u64, |s, p| s.wrapping_add(pair_hash(seed, p)));
+        state.write_u64(sum);
+    }
+}
+
+/// Calculate the hash of a single pair, well mixed, since the hashes of all
+/// pairs are summed up and must not cancel each other out.
+#[inline]
+fn pair_hash<P: Hash>(seed: u64, p: P) -> u64 {
+    let mut h = Fx(seed);
+    p.hash(&mut h);
+    let mut x = h.finish();
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff_51_af_d7_ed_55_8c_cd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4_ce_b9_fe_1a_85_ec_53);
+    x ^ (x >> 33)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+
+    fn hash_of<T: Hash>(t: &T) -> u64 {
+        let mut h = DefaultHasher::new();
+        t.hash(&mut h);
+        h.finish()
+    }
+
+    /// Pseudo-random numbers, which are the same in every run.
+    fn numbers(seed: u64) -> impl Iterator<Item = u64> {
+        let mut x = seed;
+        core::iter::repeat_with(move || {
+            x = x
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            x >> 33
+        })
+    }
+
+    #[test]
+    fn hashes_equal_maps_equally() {
+        for seed in 0..200 {
+            let pairs: Vec<(u64, u64)> = numbers(seed).take(16).map(|x| (x % 20, x % 7)).collect();
+            let a: Map<u64, u64, 20> = pairs.iter().copied().collect();
+            let mut b: Map<u64, u64, 20> = Map::new();
+            for (k, _) in pairs.iter().rev() {
+                b.insert(*k, a[k]);
+            }
+            assert_eq!(a, b);
+            assert_eq!(hash_of(&a), hash_of(&b));
+        }
+    }
+
+    #[test]
+    fn hashes_different_maps_differently() {
+        let mut hashes = HashSet::new();
+        let mut maps = 0;
+        for seed in 0..500 {
+            let m: Map<u64, u64, 8> = numbers(seed).take(4).map(|x| (x % 10, x % 3)).collect();
+            maps += 1;
+            hashes.insert(hash_of(&m));
+        }
+        assert!(hashes.len() * 10 > maps * 9);
+    }
+
+    #[test]
+    fn distinguishes_swapped_values() {
+        let a: Map<u8, u8, 4> = Map::from([(1, 2), (3, 4)]);
+        let b: Map<u8, u8, 4> = Map::from([(1, 4), (3, 2)]);
+        assert_ne!(hash_of(&a), hash_of(&b));
+        let empty: Map<u8, u8, 4> = Map::new();
+        assert_ne!(hash_of(&empty), hash_of(&Map::<u8, u8, 4>::from([(0, 0)])));
+    }
+
+    #[test]
+    fn uses_map_as_key() {
+        let mut set: HashSet<Map<&str, i32, 4>> = HashSet::new();
+        set.insert(Map::from([("x", 1), ("y", 2)]));
+        assert!(set.contains(&Map::from([("y", 2), ("x", 1)])));
+        assert!(!set.contains(&Map::from([("y", 2)])));
+    }
+
+    #[test]
+    fn hashes_pairs_with_seed() {
+        let seeds: HashSet<u64> = (0..100).map(|seed| pair_hash(seed, (1u8, 2u8))).collect();
+        assert_eq!(100, seeds.len());
+        assert_eq!(pair_hash(7, (&1u8, &2u8)), pair_hash(7, (1u8, 2u8)));
+    }
+}
@@ -18,23 +18,25 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Map;
+use crate::{KeyEq, Map};
 use core::borrow::Borrow;
 use core::ops::{Index, IndexMut};
 
-impl<K: Eq + Borrow<Q>, Q: Eq + ?Sized, V, const N: usize> Index<&Q> for Map<K, V, N> {
+impl<K: PartialEq + Borrow<Q>, Q: ?Sized, V, const N: usize, E: KeyEq<Q>> Index<&Q>
+    for Map<K, V, N, E>
+{
     type Output = V;
 
     #[inline]
-    #[must_use]
     fn index(&self, key: &Q) -> &V {
         self.get(key).expect("No entry found for the key")
     }
 }
 
-impl<K: Eq + Borrow<Q>, Q: Eq + ?Sized, V, const N: usize> IndexMut<&Q> for Map<K, V, N> {
+impl<K: PartialEq + Borrow<Q>, Q: ?Sized, V, const N: usize, E: KeyEq<Q>> IndexMut<&Q>
+    for Map<K, V, N, E>
+{
     #[inline]
-    #[must_use]
     fn index_mut(&mut self, key: &Q) -> &mut V {
         self.get_mut(key).expect("No entry found for the key")
     }
@@ -61,6 +63,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::should_panic_without_expect, clippy::unused_unit)]
     #[should_panic]
     fn wrong_index() -> () {
         let mut m: Map<&str, i32, 10> = Map::new();
@@ -18,47 +18,240 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::{IntoIter, Iter, IterMut, Map};
-use core::mem;
+use crate::{Drain, ExtractIf, IntoIter, Iter, IterMut, Map, SortedMap};
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
 use core::mem::MaybeUninit;
+use core::ops::{Bound, RangeBounds};
+use core::{ptr, slice};
 
-impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
+impl<K: PartialEq, V, const N: usize, E> Map<K, V, N, E> {
     /// Make an iterator over all pairs.
     #[inline]
     #[must_use]
-    pub const fn iter(&self) -> Iter<K, V, N> {
+    pub const fn iter(&self) -> Iter<'_, K, V, N> {
         Iter {
-            next: self.next,
+            next: self.len,
             pos: 0,
             pairs: &self.pairs,
         }
     }
 
     /// An iterator with mutable references to the values but
+    /// immutable references to the keys.
     #[inline]
-    pub fn iter_mut(&mut self) -> IterMut<K, V> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
         IterMut {
-            next: self.next,
+            iter: self.pairs[..self.len].iter_mut(),
+        }
+    }
+
+    /// Remove all pairs from the map, returning them in an iterator
+    /// and keeping the space intact for future use.
+    ///
+    /// The map is empty as soon as the iterator is made. The pairs that
+    /// are not taken from the iterator are dropped together with it.
+    /// If one of the destructors panics, the rest of the pairs are
+    /// still dropped.
+    ///
+    /// ```
+    /// let mut m: micromap::Map<u8, &str, 4> = micromap::Map::from([(1, "one"), (2, "two")]);
+    /// assert_eq!(vec![(1, "one"), (2, "two")], m.drain().collect::<Vec<_>>());
+    /// assert!(m.is_empty());
+    /// ```
+    #[inline]
+    pub const fn drain(&mut self) -> Drain<'_, K, V, N, E> {
+        let busy = self.len;
+        self.len = 0;
+        Drain {
             pos: 0,
-            iter: self.pairs.iter_mut(),
+            busy,
+            map: self,
+        }
+    }
+
+    /// Make an iterator, which removes the pairs matching the predicate from
+    /// the map and returns them, lazily, one by one.
+    ///
+    /// The predicate may modify the values of all pairs it visits.
+    /// If the iterator is dropped before the end, the pairs not yet visited
+    /// stay in the map, just like when the predicate panics. The order of
+    /// the pairs that stay doesn't change.
+    ///
+    /// ```
+    /// let mut m: micromap::Map<u8, u8, 8> = (0..8).map(|i| (i, i)).collect();
+    /// let odd: Vec<_> = m.extract_if(|k, _| k % 2 == 1).collect();
+    /// assert_eq!(vec![(1, 1), (3, 3), (5, 5), (7, 7)], odd);
+    /// assert_eq!(vec![&0, &2, &4, &6], m.keys().collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    pub const fn extract_if<F: FnMut(&K, &mut V) -> bool>(
+        &mut self,
+        pred: F,
+    ) -> ExtractIf<'_, K, V, N, F, E> {
+        let busy = self.len;
+        self.len = 0;
+        ExtractIf {
+            map: self,
+            busy,
+            seen: 0,
+            removed: 0,
+            pred,
+        }
+    }
+}
+
+impl<K: Ord, V, const N: usize> SortedMap<K, V, N> {
+    /// Make an iterator over all pairs, in the order of their keys.
+    #[inline]
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, K, V, N> {
+        self.map.iter()
+    }
+
+    /// An iterator with mutable references to the values but
+    /// immutable references to the keys, in the order of the keys.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// Make an iterator over the pairs with the keys in the range,
+    /// in the order of their keys.
+    ///
+    /// If the start of the range is after its end, the iterator is empty.
+    /// For example:
+    ///
+    /// ```
+    /// let m: micromap::SortedMap<i32, i32, 8> = (0..8).map(|i| (i, i * 10)).collect();
+    /// assert_eq!(vec![(&2, &20), (&3, &30)], m.range(2..4).collect::<Vec<_>>());
+    /// assert_eq!(vec![(&6, &60), (&7, &70)], m.range(6..).collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    pub fn range<Q: Ord + ?Sized, R: RangeBounds<Q>>(&self, range: R) -> Iter<'_, K, V, N>
+    where
+        K: Borrow<Q>,
+    {
+        let (lo, hi) = self.bounds(&range);
+        Iter {
+            next: hi,
+            pos: lo,
+            pairs: &self.map.pairs,
+        }
+    }
+
+    /// Make a mutable iterator over the pairs with the keys in the range,
+    /// in the order of their keys.
+    #[inline]
+    pub fn range_mut<Q: Ord + ?Sized, R: RangeBounds<Q>>(&mut self, range: R) -> IterMut<'_, K, V>
+    where
+        K: Borrow<Q>,
+    {
+        let (lo, hi) = self.bounds(&range);
+        IterMut {
+            iter: self.map.pairs[lo..hi].iter_mut(),
         }
     }
+
+    /// Internal function to find the positions of the first pair in the range
+    /// and of the first pair after it.
+    #[inline]
+    fn bounds<Q: Ord + ?Sized, R: RangeBounds<Q>>(&self, range: &R) -> (usize, usize)
+    where
+        K: Borrow<Q>,
+    {
+        let pairs = self.map.as_slice();
+        let lo = match range.start_bound() {
+            Bound::Included(s) => pairs.partition_point(|p| p.0.borrow() < s),
+            Bound::Excluded(s) => pairs.partition_point(|p| p.0.borrow() <= s),
+            Bound::Unbounded => 0,
+        };
+        let hi = match range.end_bound() {
+            Bound::Included(e) => pairs.partition_point(|p| p.0.borrow() <= e),
+            Bound::Excluded(e) => pairs.partition_point(|p| p.0.borrow() < e),
+            Bound::Unbounded => pairs.len(),
+        };
+        (lo, hi.max(lo))
+    }
+}
+
+/// Internal function to look at the occupied part of the array as pairs.
+///
+/// The caller must make sure all of them are initialized.
+#[inline]
+const unsafe fn assumed<K, V>(pairs: &[MaybeUninit<(K, V)>]) -> &[(K, V)] {
+    unsafe { slice::from_raw_parts(pairs.as_ptr().cast(), pairs.len()) }
+}
+
+impl<K, V> IterMut<'_, K, V> {
+    /// Internal function to get the pairs not visited yet.
+    #[inline]
+    pub(crate) fn as_slice(&self) -> &[(K, V)] {
+        unsafe { assumed(self.iter.as_slice()) }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> IntoIter<K, V, N> {
+    /// Internal function to get the pairs not taken yet.
+    #[inline]
+    pub(crate) fn as_slice(&self) -> &[(K, V)] {
+        unsafe { assumed(&self.map.pairs[self.pos..self.map.len]) }
+    }
 }
 
 impl<'a, K, V, const N: usize> Iterator for Iter<'a, K, V, N> {
     type Item = (&'a K, &'a V);
 
     #[inline]
-    #[must_use]
     fn next(&mut self) -> Option<Self::Item> {
-        while self.pos < self.next {
+        if self.pos < self.next {
             let p = unsafe { self.pairs[self.pos].assume_init_ref() };
             self.pos += 1;
-            if let Some(p) = p {
-                return Some((&p.0, &p.1));
-            }
+            Some((&p.0, &p.1))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.next - self.pos;
+        (n, Some(n))
+    }
+}
+
+impl<K, V, const N: usize> DoubleEndedIterator for Iter<'_, K, V, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pos < self.next {
+            self.next -= 1;
+            let p = unsafe { self.pairs[self.next].assume_init_ref() };
+            Some((&p.0, &p.1))
+        } else {
+            None
+        }
+    }
+}
+
+impl<K, V, const N: usize> ExactSizeIterator for Iter<'_, K, V, N> {}
+
+impl<K, V, const N: usize> FusedIterator for Iter<'_, K, V, N> {}
+
+impl<K, V, const N: usize> Clone for Iter<'_, K, V, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            next: self.next,
+            pos: self.pos,
+            pairs: self.pairs,
         }
-        None
+    }
+}
+
+impl<K: Debug, V: Debug, const N: usize> Debug for Iter<'_, K, V, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
     }
 }
 
@@ -67,48 +260,211 @@ impl<'a, K, V> Iterator for IterMut<'a, K, V> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        while self.pos < self.next {
-            let p = unsafe { self.iter.next().unwrap().assume_init_mut() };
+        self.iter.next().map(|p| {
+            let p = unsafe { p.assume_init_mut() };
+            (&p.0, &mut p.1)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IterMut<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|p| {
+            let p = unsafe { p.assume_init_mut() };
+            (&p.0, &mut p.1)
+        })
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for IterMut<'_, K, V> {}
+
+impl<K: Debug, V: Debug> Debug for IterMut<'_, K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.as_slice().iter().map(|p| (&p.0, &p.1)))
+            .finish()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Iterator for IntoIter<K, V, N> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos < self.map.len {
+            let p = unsafe { self.map.pairs[self.pos].assume_init_read() };
             self.pos += 1;
-            if let Some(p) = p {
-                return Some((&p.0, &mut p.1));
-            }
+            Some(p)
+        } else {
+            None
         }
-        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.map.len - self.pos;
+        (n, Some(n))
     }
 }
 
-impl<K: PartialEq, V, const N: usize> Iterator for IntoIter<K, V, N> {
+impl<K: PartialEq, V, const N: usize> DoubleEndedIterator for IntoIter<K, V, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pos < self.map.len {
+            self.map.len -= 1;
+            Some(unsafe { self.map.pairs[self.map.len].assume_init_read() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> ExactSizeIterator for IntoIter<K, V, N> {}
+
+impl<K: PartialEq, V, const N: usize> FusedIterator for IntoIter<K, V, N> {}
+
+impl<K: PartialEq + Debug, V: Debug, const N: usize> Debug for IntoIter<K, V, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Drop for IntoIter<K, V, N> {
+    fn drop(&mut self) {
+        let busy = self.map.len;
+        self.map.len = 0;
+        unsafe {
+            let pairs = self.map.pairs.get_unchecked_mut(self.pos..busy);
+            ptr::drop_in_place(ptr::from_mut(pairs) as *mut [(K, V)]);
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize, E> Iterator for Drain<'_, K, V, N, E> {
     type Item = (K, V);
 
     #[inline]
-    #[must_use]
     fn next(&mut self) -> Option<Self::Item> {
-        while self.pos < self.map.next {
-            let p = &mut self.map.pairs[self.pos];
+        if self.pos < self.busy {
+            let p = unsafe { self.map.pairs[self.pos].assume_init_read() };
             self.pos += 1;
-            unsafe {
-                if p.assume_init_ref().is_some() {
-                    return mem::replace(p, MaybeUninit::new(None)).assume_init();
-                }
+            Some(p)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.busy - self.pos;
+        (n, Some(n))
+    }
+}
+
+impl<K: PartialEq, V, const N: usize, E> DoubleEndedIterator for Drain<'_, K, V, N, E> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pos < self.busy {
+            self.busy -= 1;
+            Some(unsafe { self.map.pairs[self.busy].assume_init_read() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize, E> ExactSizeIterator for Drain<'_, K, V, N, E> {}
+
+impl<K: PartialEq, V, const N: usize, E> FusedIterator for Drain<'_, K, V, N, E> {}
+
+impl<K: PartialEq + Debug, V: Debug, const N: usize, E> Debug for Drain<'_, K, V, N, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rest = unsafe { assumed(&self.map.pairs[self.pos..self.busy]) };
+        f.debug_list().entries(rest).finish()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize, E> Drop for Drain<'_, K, V, N, E> {
+    fn drop(&mut self) {
+        unsafe {
+            let pairs = self.map.pairs.get_unchecked_mut(self.pos..self.busy);
+            self.pos = self.busy;
+            ptr::drop_in_place(ptr::from_mut(pairs) as *mut [(K, V)]);
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize, F: FnMut(&K, &mut V) -> bool, E> Iterator
+    for ExtractIf<'_, K, V, N, F, E>
+{
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.seen < self.busy {
+            let i = self.seen;
+            let p = unsafe { self.map.pairs[i].assume_init_mut() };
+            let take = (self.pred)(&p.0, &mut p.1);
+            self.seen += 1;
+            if take {
+                self.removed += 1;
+                return Some(unsafe { self.map.pairs[i].assume_init_read() });
+            }
+            if self.removed > 0 {
+                self.map.pairs.swap(i - self.removed, i);
             }
         }
         None
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.busy - self.seen))
+    }
 }
 
-impl<'a, K: PartialEq, V, const N: usize> IntoIterator for &'a Map<K, V, N> {
+impl<K: PartialEq, V, const N: usize, F: FnMut(&K, &mut V) -> bool, E> FusedIterator
+    for ExtractIf<'_, K, V, N, F, E>
+{
+}
+
+impl<K: PartialEq, V, const N: usize, F, E> Drop for ExtractIf<'_, K, V, N, F, E> {
+    /// Close the gap left by the removed pairs, keeping the pairs
+    /// not yet visited in the map.
+    fn drop(&mut self) {
+        if self.removed > 0 {
+            unsafe {
+                let base = self.map.pairs.as_mut_ptr();
+                ptr::copy(
+                    base.add(self.seen),
+                    base.add(self.seen - self.removed),
+                    self.busy - self.seen,
+                );
+            }
+        }
+        self.map.len = self.busy - self.removed;
+    }
+}
+
+impl<'a, K: PartialEq, V, const N: usize, E> IntoIterator for &'a Map<K, V, N, E> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V, N>;
 
     #[inline]
-    #[must_use]
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
 
-impl<'a, K: PartialEq, V, const N: usize> IntoIterator for &'a mut Map<K, V, N> {
+impl<'a, K: PartialEq, V, const N: usize, E> IntoIterator for &'a mut Map<K, V, N, E> {
     type Item = (&'a K, &'a mut V);
     type IntoIter = IterMut<'a, K, V>;
 
@@ -123,12 +479,41 @@ impl<K: PartialEq, V, const N: usize> IntoIterator for Map<K, V, N> {
     type IntoIter = IntoIter<K, V, N>;
 
     #[inline]
-    #[must_use]
     fn into_iter(self) -> Self::IntoIter {
         IntoIter { pos: 0, map: self }
     }
 }
 
+impl<'a, K: Ord, V, const N: usize> IntoIterator for &'a SortedMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize> IntoIterator for &'a mut SortedMap<K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: Ord, V, const N: usize> IntoIterator for SortedMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -150,6 +535,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::explicit_iter_loop)]
     fn insert_and_iterate() {
         let mut m: Map<&str, i32, 10> = Map::new();
         m.insert("one", 42);
@@ -174,6 +560,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::explicit_iter_loop)]
     fn iterate_with_blanks() {
         let mut m: Map<&str, i32, 10> = Map::new();
         m.insert("one", 1);
@@ -202,6 +589,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::explicit_iter_loop)]
     fn change_with_iter_mut() {
         let mut m: Map<&str, i32, 10> = Map::new();
         m.insert("one", 2);
@@ -240,6 +628,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::cast_sign_loss)]
     fn into_iter_drop() {
         use std::rc::Rc;
         let mut m: Map<i32, Rc<()>, 8> = Map::new();
@@ -252,4 +641,186 @@ mod test {
         let _p = m.into_iter().nth(3);
         assert_eq!(Rc::strong_count(&v), 2); // v & p
     }
+
+    #[test]
+    fn iterates_from_both_ends_with_blanks() {
+        let mut m: Map<u8, u8, 8> = (0..6).map(|i| (i, i)).collect();
+        m.remove(&1);
+        m.remove(&4);
+        let mut it = m.iter();
+        assert_eq!(4, it.len());
+        assert_eq!(Some((&0, &0)), it.next());
+        assert_eq!(Some((&3, &3)), it.next_back());
+        assert_eq!((2, Some(2)), it.size_hint());
+        assert_eq!(Some((&5, &5)), it.next());
+        assert_eq!(Some((&2, &2)), it.next_back());
+        assert_eq!(0, it.len());
+        assert_eq!(None, it.next());
+        assert_eq!(None, it.next_back());
+        assert_eq!(None, it.next());
+    }
+
+    #[test]
+    fn reverses_all_iterators() {
+        let mut m: Map<u8, u8, 8> = (0..4).map(|i| (i, i * 10)).collect();
+        assert_eq!(
+            vec![3, 2, 1, 0],
+            m.iter().rev().map(|p| *p.0).collect::<Vec<_>>()
+        );
+        for (_, v) in m.iter_mut().rev().take(2) {
+            *v += 1;
+        }
+        assert_eq!(4, m.iter_mut().len());
+        assert_eq!(
+            vec![(3, 31), (2, 21), (1, 10), (0, 0)],
+            m.clone().into_iter().rev().collect::<Vec<_>>()
+        );
+        let mut it = m.into_iter();
+        assert_eq!(Some((3, 31)), it.next_back());
+        assert_eq!(Some((0, 0)), it.next());
+        assert_eq!(2, it.len());
+    }
+
+    #[test]
+    fn into_iter_drops_the_middle() {
+        use std::rc::Rc;
+        let v = Rc::new(());
+        let m: Map<usize, Rc<()>, 8> = (0..6).map(|i| (i, Rc::clone(&v))).collect();
+        let mut it = m.into_iter();
+        drop(it.next());
+        drop(it.next_back());
+        assert_eq!(5, Rc::strong_count(&v));
+        drop(it);
+        assert_eq!(1, Rc::strong_count(&v));
+    }
+
+    #[test]
+    fn clones_and_prints_iterators() {
+        let m: Map<&str, u8, 4> = Map::from([("a", 1), ("b", 2)]);
+        let mut it = m.iter();
+        it.next();
+        let copy = it.clone();
+        assert_eq!(vec![(&"b", &2)], copy.collect::<Vec<_>>());
+        assert_eq!(r#"[("b", 2)]"#, format!("{it:?}"));
+        assert_eq!(
+            r#"[("a", 1), ("b", 2)]"#,
+            format!("{:?}", m.clone().into_iter())
+        );
+        let mut n = m.clone();
+        assert_eq!(r#"[("a", 1), ("b", 2)]"#, format!("{:?}", n.iter_mut()));
+        assert_eq!(r#"[("a", 1), ("b", 2)]"#, format!("{:?}", n.drain()));
+    }
+
+    #[test]
+    fn drains_from_both_ends() {
+        let mut m: Map<u8, u8, 4> = (0..4).map(|i| (i, i)).collect();
+        let mut d = m.drain();
+        assert_eq!(4, d.len());
+        assert_eq!(Some((3, 3)), d.next_back());
+        assert_eq!(Some((0, 0)), d.next());
+        assert_eq!(2, d.len());
+        drop(d);
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn drains_and_reuses() {
+        let mut m: Map<u8, String, 4> = Map::new();
+        m.insert(1, "one".to_string());
+        m.insert(2, "two".to_string());
+        let mut d = m.drain();
+        assert_eq!(Some((1, "one".to_string())), d.next());
+        drop(d);
+        assert!(m.is_empty());
+        m.insert(3, "three".to_string());
+        assert_eq!(
+            vec![(3, "three".to_string())],
+            m.drain().collect::<Vec<_>>()
+        );
+        assert!(m.drain().next().is_none());
+    }
+
+    #[test]
+    fn drain_drops_the_rest() {
+        use std::rc::Rc;
+        let v = Rc::new(());
+        let mut m: Map<usize, Rc<()>, 8> = (0..8).map(|i| (i, Rc::clone(&v))).collect();
+        let p = m.drain().nth(2);
+        assert_eq!(Rc::strong_count(&v), 2);
+        drop(p);
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn extracts_lazily() {
+        let mut m: Map<u8, u8, 8> = (0..8).map(|i| (i, i)).collect();
+        let mut e = m.extract_if(|k, v| {
+            *v += 10;
+            k % 3 == 0
+        });
+        assert_eq!(Some((0, 10)), e.next());
+        assert_eq!(Some((3, 13)), e.next());
+        drop(e);
+        assert_eq!(
+            vec![(1, 11), (2, 12), (4, 4), (5, 5), (6, 6), (7, 7)],
+            m.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn extracts_nothing_and_everything() {
+        let mut m: Map<u8, u8, 4> = (0..4).map(|i| (i, i)).collect();
+        assert_eq!(0, m.extract_if(|_, _| false).count());
+        assert_eq!(4, m.len());
+        assert_eq!(4, m.extract_if(|_, _| true).count());
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn iterates_sorted_map_in_order() {
+        let mut m: SortedMap<i32, i32, 8> = SortedMap::new();
+        for k in [5, 1, 4, 2, 3] {
+            m.insert(k, k * 10);
+        }
+        for (_, v) in &mut m {
+            *v += 1;
+        }
+        assert_eq!(
+            vec![(&1, &11), (&2, &21), (&3, &31), (&4, &41), (&5, &51)],
+            m.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![1, 2, 3, 4, 5],
+            m.into_iter().map(|p| p.0).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn iterates_ranges() {
+        let m: SortedMap<i32, i32, 8> = [1, 3, 5, 7].into_iter().map(|k| (k, k)).collect();
+        let keys = |r: Iter<'_, i32, i32, 8>| r.map(|p| *p.0).collect::<Vec<_>>();
+        assert_eq!(vec![3, 5], keys(m.range(2..7)));
+        assert_eq!(vec![3, 5, 7], keys(m.range(3..=7)));
+        assert_eq!(
+            vec![5, 7],
+            keys(m.range((Bound::Excluded(3), Bound::Unbounded)))
+        );
+        assert_eq!(vec![1, 3], keys(m.range(..5)));
+        assert_eq!(vec![1, 3, 5, 7], keys(m.range(..)));
+        assert!(keys(m.range(8..)).is_empty());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = m.range(6..2);
+        assert!(keys(reversed).is_empty());
+    }
+
+    #[test]
+    fn changes_values_in_range() {
+        let mut m: SortedMap<i32, i32, 8> = (0..8).map(|k| (k, 0)).collect();
+        for (_, v) in m.range_mut(2..4) {
+            *v = 1;
+        }
+        assert_eq!(2, m.values().sum::<i32>());
+        assert_eq!(Some(&1), m.get(&3));
+        assert_eq!(Some(&0), m.get(&4));
+    }
 }
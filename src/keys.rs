@@ -18,16 +18,20 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::{IntoKeys, Keys, Map};
+use crate::{IntoKeys, Keys, Map, SortedMap};
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
 
-impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
-    /// An iterator visiting all keys in arbitrary order.
+impl<K: PartialEq, V, const N: usize, E> Map<K, V, N, E> {
+    /// An iterator visiting all keys in the order of their positions.
     #[inline]
     pub const fn keys(&self) -> Keys<'_, K, V, N> {
         Keys { iter: self.iter() }
     }
+}
 
-    /// Consuming iterator visiting all keys in arbitrary order.
+impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
+    /// Consuming iterator visiting all keys in the order of their positions.
     #[inline]
     pub fn into_keys(self) -> IntoKeys<K, V, N> {
         IntoKeys {
@@ -36,6 +40,21 @@ impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
     }
 }
 
+impl<K: Ord, V, const N: usize> SortedMap<K, V, N> {
+    /// An iterator visiting all keys in their order.
+    #[inline]
+    #[must_use]
+    pub const fn keys(&self) -> Keys<'_, K, V, N> {
+        self.map.keys()
+    }
+
+    /// Consumes the map and returns an iterator over all keys, in their order.
+    #[inline]
+    pub fn into_keys(self) -> IntoKeys<K, V, N> {
+        self.map.into_keys()
+    }
+}
+
 impl<'a, K: PartialEq, V, const N: usize> Iterator for Keys<'a, K, V, N> {
     type Item = &'a K;
 
@@ -43,6 +62,37 @@ impl<'a, K: PartialEq, V, const N: usize> Iterator for Keys<'a, K, V, N> {
     fn next(&mut self) -> Option<Self::Item> {
         self.iter.next().map(|p| p.0)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> DoubleEndedIterator for Keys<'_, K, V, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|p| p.0)
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> ExactSizeIterator for Keys<'_, K, V, N> {}
+
+impl<K: PartialEq, V, const N: usize> FusedIterator for Keys<'_, K, V, N> {}
+
+impl<K: PartialEq, V, const N: usize> Clone for Keys<'_, K, V, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<K: PartialEq + Debug, V, const N: usize> Debug for Keys<'_, K, V, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
 }
 
 impl<K: PartialEq, V, const N: usize> Iterator for IntoKeys<K, V, N> {
@@ -52,6 +102,30 @@ impl<K: PartialEq, V, const N: usize> Iterator for IntoKeys<K, V, N> {
     fn next(&mut self) -> Option<K> {
         self.iter.next().map(|p| p.0)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> DoubleEndedIterator for IntoKeys<K, V, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<K> {
+        self.iter.next_back().map(|p| p.0)
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> ExactSizeIterator for IntoKeys<K, V, N> {}
+
+impl<K: PartialEq, V, const N: usize> FusedIterator for IntoKeys<K, V, N> {}
+
+impl<K: PartialEq + Debug, V, const N: usize> Debug for IntoKeys<K, V, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter.as_slice().iter().map(|p| &p.0))
+            .finish()
+    }
 }
 
 #[cfg(test)]
@@ -67,6 +141,25 @@ mod test {
         assert_eq!(m.keys().collect::<Vec<_>>(), [&"foo", &"bar"]);
     }
 
+    #[test]
+    fn iterate_keys_backwards() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        m.insert("foo", 0);
+        m.insert("bar", 0);
+        m.insert("baz", 0);
+        m.remove("bar");
+        let keys = m.keys();
+        assert_eq!(2, keys.len());
+        assert_eq!(r#"["foo", "baz"]"#, format!("{:?}", keys.clone()));
+        assert_eq!(keys.rev().collect::<Vec<_>>(), [&"baz", &"foo"]);
+        let mut into = m.into_keys();
+        assert_eq!(Some("baz"), into.next_back());
+        assert_eq!(r#"["foo"]"#, format!("{into:?}"));
+        assert_eq!(Some("foo"), into.next_back());
+        assert_eq!(None, into.next());
+        assert_eq!(None, into.next_back());
+    }
+
     #[test]
     fn iterate_into_keys() {
         let mut m: Map<&str, i32, 10> = Map::new();
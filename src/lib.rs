@@ -42,6 +42,17 @@
 //! what the third type argument `10` is for, in the example above. The array
 //! will have exactly ten elements. An attempt to add an 11th element will lead
 //! to a panic.
+//!
+//! With the `simd` feature, lookups of primitive unsigned integer keys (`u8`,
+//! `u16`, `u32`, `u64` and `usize`) compare many keys at once, wherever the
+//! keys are packed together: in [`SplitMap`], and in [`Map`] when the values
+//! take no space, as in `Map<u32, (), N>` and in [`Set`]. On x86-64 this is
+//! done with SSE2 instructions, while on other CPUs a portable chunked
+//! comparison is used. All other keys are compared one by one, as usual.
+//!
+//! The feature doesn't speed up a [`Map`] with values that take space,
+//! such as `Map<u32, u64, N>`, since its keys are interleaved with the values.
+//! Use [`SplitMap`] instead, if such a map needs faster lookups.
 
 #![cfg_attr(all(not(feature = "std"), not(doc), not(test)), no_std)]
 #![doc(html_root_url = "https://docs.rs/micromap/0.0.0")]
@@ -50,21 +61,41 @@
 #![allow(clippy::multiple_inherent_impl)]
 #![allow(clippy::multiple_crate_versions)]
 
-#[cfg(feature = "std")]
-mod debug;
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+mod adaptive;
 mod clone;
+mod comparators;
 mod ctors;
+mod debug;
+mod entry;
 mod eq;
+mod error;
 mod from;
+mod hash;
 mod index;
 mod iterators;
 mod keys;
+mod macros;
 mod map;
+mod ord;
+mod positional;
+#[cfg(feature = "serde")]
+pub mod serde;
 #[cfg(feature = "serde")]
 mod serialization;
+mod set;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "alloc")]
+mod small;
+mod sorted;
+mod split;
+mod tagged;
 mod values;
 
+use core::marker::PhantomData;
 use core::mem::MaybeUninit;
 
 /// A faster alternative of [`std::collections::HashMap`].
@@ -89,31 +120,123 @@ use core::mem::MaybeUninit;
 ///
 /// It is also faster because it doesn't grow in size. When a [`Map`] is created,
 /// its size is fixed on stack. If an attempt is made to insert too many keys
-/// into it, it simply panics. Moreover, in the "release" mode it doesn't panic,
-/// but its behaviour is undefined. In the "release" mode all boundary checks
-/// are disabled, for the sake of higher performance.
-pub struct Map<K: PartialEq, V, const N: usize> {
-    /// The next available pair in the array.
-    next: usize,
+/// into it, it simply panics, both in "debug" and "release" modes. If you
+/// prefer to handle the overflow, use [`Map::try_insert`], which returns
+/// a [`CapacityError`] instead. If you are absolutely sure there is enough space,
+/// use the `unsafe` [`Map::insert_unchecked`], which doesn't check anything.
+///
+/// All pairs are packed at the front of the array, without holes between them,
+/// and the number of them is stored in the map. When a pair is removed, the last
+/// pair takes its place. Thus, [`Map::len`] doesn't need to count anything and
+/// the lookups never skip over empty slots.
+///
+/// The pairs are iterated in the order of their positions, which is
+/// the order of their insertion only as long as nothing reorders them.
+/// These operations keep the order: [`Map::insert`] puts a new key at the end
+/// and leaves an existing key where it is, while [`Map::shift_remove`],
+/// [`Map::retain`] and [`Map::extract_if`] close the gaps without moving
+/// the remaining pairs around. These operations change the order:
+/// [`Map::remove`], [`Map::swap_remove`] and the removals through
+/// an [`Entry`] move the last pair to the place of the removed one,
+/// the lookups with [`Map::get_and_transpose`] and [`Map::get_and_move_to_front`]
+/// move the found pairs closer to the front, and [`Map::move_index`] with
+/// [`Map::swap_indices`] move the pairs to the given positions. The pairs
+/// may also be accessed by their positions, similar to how
+/// [`IndexMap`](https://docs.rs/indexmap) works:
+///
+/// ```
+/// let mut m: micromap::Map<&str, i32, 4> = micromap::Map::new();
+/// m.insert("one", 1);
+/// m.insert("two", 2);
+/// m.insert("three", 3);
+/// assert_eq!(Some(1), m.get_index_of("two"));
+/// m.shift_remove("one");
+/// assert_eq!(Some((&"two", &2)), m.first());
+/// assert_eq!(Some(("three", 3)), m.pop());
+/// ```
+///
+/// The keys are compared with their [`PartialEq`], unless another [`KeyEq`]
+/// is given as the fourth type argument, for example [`CaseInsensitive`]:
+///
+/// ```
+/// use micromap::{CaseInsensitive, Map};
+/// let mut m: Map<&str, i32, 4, CaseInsensitive> = Map::with_key_eq(CaseInsensitive);
+/// m.insert("Content-Type", 1);
+/// assert_eq!(Some(&1), m.get("content-type"));
+/// ```
+///
+/// Such a map supports the lookups, insertions, removals, iterations,
+/// [`Map::entry`] and `==`, all comparing the keys through the [`KeyEq`].
+/// The rest of the API is available only for the default [`DefaultEq`],
+/// as listed in the docs of [`KeyEq`].
+///
+/// With the `simd` feature, primitive keys are compared many at once only
+/// when the value type takes no space, as in `Map<u32, (), N>`, since only
+/// then the keys are packed together. A value of non-zero size turns
+/// the SIMD lookup off. Use [`SplitMap`] to keep it with such values.
+pub struct Map<K: PartialEq, V, const N: usize, E = DefaultEq> {
+    /// The total number of pairs, which are all packed at the front of the array.
+    len: usize,
     /// The fixed-size array of key-value pairs.
-    pairs: [MaybeUninit<Option<(K, V)>>; N],
+    pairs: [MaybeUninit<(K, V)>; N],
+    /// The way the keys are compared.
+    eq: PhantomData<E>,
+}
+
+/// A way to compare the keys of a [`Map`], instead of their [`PartialEq`].
+///
+/// It must behave like an equivalence: reflexive, symmetric and transitive.
+/// Since the keys are borrowed for lookups, it has to be implemented
+/// for the borrowed forms as well, consistently with the keys themselves,
+/// for example for both `String` and `str`.
+///
+/// A [`Map`] with a custom [`KeyEq`] has the lookups, insertions, removals,
+/// iterations, [`Map::entry`], [`PartialEq`] and [`Eq`]. These are available
+/// only with the [`DefaultEq`], since they compare keys with [`PartialEq`]
+/// or don't know how to hash or order them consistently with another
+/// comparison: the positional methods, such as [`Map::get_index_of`] and
+/// [`Map::shift_remove`], the lookups [`Map::get_and_transpose`] and
+/// [`Map::get_and_move_to_front`], the consuming [`Map::into_iter`],
+/// [`Map::into_keys`] and [`Map::into_values`], [`Map::new`],
+/// [`Map::from_array`], the [`From`] and [`TryFrom`] conversions, [`Hash`](core::hash::Hash),
+/// [`Map::cmp_by_sorted_keys`] and serialization with `serde`.
+pub trait KeyEq<Q: ?Sized> {
+    /// Is it the same as [`PartialEq`], which allows lookups to compare
+    /// primitive keys many at once, with the `simd` feature?
+    const NATIVE: bool = false;
+
+    /// Are the two keys equal?
+    fn eq(a: &Q, b: &Q) -> bool;
 }
 
+/// The default [`KeyEq`] of a [`Map`], which compares the keys with their
+/// [`PartialEq`].
+#[derive(Clone, Copy, Default, Debug)]
+pub struct DefaultEq;
+
+/// A [`KeyEq`], which compares strings and bytes ignoring the case of ASCII
+/// letters, like HTTP header names and most identifiers.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct CaseInsensitive;
+
+/// A [`KeyEq`], which compares floating point numbers by their bit patterns,
+/// so `NaN` is equal to itself, while `0.0` is not equal to `-0.0`.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct ByBits;
+
 /// Iterator over the [`Map`].
 pub struct Iter<'a, K, V, const N: usize> {
-    /// The next available pair in the array.
+    /// The total number of pairs in the array.
     next: usize,
     /// The next position in the iterator to read.
     pos: usize,
     /// The fixed-size array of key-value pairs.
-    pairs: &'a [MaybeUninit<Option<(K, V)>>; N],
+    pairs: &'a [MaybeUninit<(K, V)>; N],
 }
 
 /// Mutable Iterator over the [`Map`].
 pub struct IterMut<'a, K, V> {
-    next: usize,
-    pos: usize,
-    iter: core::slice::IterMut<'a, MaybeUninit<Option<(K, V)>>>,
+    iter: core::slice::IterMut<'a, MaybeUninit<(K, V)>>,
 }
 
 /// Into-iterator over the [`Map`].
@@ -122,6 +245,31 @@ pub struct IntoIter<K: PartialEq, V, const N: usize> {
     map: Map<K, V, N>,
 }
 
+/// Draining iterator over the [`Map`], made by [`Map::drain`].
+pub struct Drain<'a, K: PartialEq, V, const N: usize, E = DefaultEq> {
+    /// The next position in the iterator to read.
+    pos: usize,
+    /// The total number of pairs, which were in the map.
+    busy: usize,
+    /// The map, which is already empty, while its pairs are moved out.
+    map: &'a mut Map<K, V, N, E>,
+}
+
+/// Iterator over the [`Map`], which removes the pairs matching a predicate,
+/// made by [`Map::extract_if`].
+pub struct ExtractIf<'a, K: PartialEq, V, const N: usize, F, E = DefaultEq> {
+    /// The map, which is shorter by `busy` pairs, while they are visited.
+    map: &'a mut Map<K, V, N, E>,
+    /// The total number of pairs, which were in the map.
+    busy: usize,
+    /// The number of pairs already visited.
+    seen: usize,
+    /// The number of pairs already removed.
+    removed: usize,
+    /// The predicate, which tells which pairs to remove.
+    pred: F,
+}
+
 /// An iterator over the values of the [`Map`].
 pub struct Values<'a, K: PartialEq, V, const N: usize> {
     iter: Iter<'a, K, V, N>,
@@ -146,3 +294,232 @@ pub struct Keys<'a, K: PartialEq, V, const N: usize> {
 pub struct IntoKeys<K: PartialEq, V, const N: usize> {
     iter: IntoIter<K, V, N>,
 }
+
+/// A view into a single entry in a [`Map`], which may either be vacant or occupied.
+///
+/// This `enum` is constructed from the [`Map::entry`] method.
+pub enum Entry<'a, K: PartialEq, V, const N: usize, E = DefaultEq> {
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'a, K, V, N, E>),
+    /// A vacant entry.
+    Vacant(VacantEntry<'a, K, V, N, E>),
+}
+
+/// A view into an occupied entry in a [`Map`]. It is part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, K: PartialEq, V, const N: usize, E = DefaultEq> {
+    /// The position of the pair in the array.
+    index: usize,
+    /// The map the entry belongs to.
+    map: &'a mut Map<K, V, N, E>,
+}
+
+/// A view into a vacant entry in a [`Map`]. It is part of the [`Entry`] enum.
+pub struct VacantEntry<'a, K: PartialEq, V, const N: usize, E = DefaultEq> {
+    /// The key that was used for the lookup.
+    key: K,
+    /// The map the entry belongs to.
+    map: &'a mut Map<K, V, N, E>,
+}
+
+/// An error returned when there is no more space in the [`Map`] for a new pair.
+///
+/// The pair that didn't fit is not lost, it can be taken back
+/// with [`CapacityError::into_pair`]. When many pairs are inserted at once,
+/// for example by [`Map::try_from_iter`], [`CapacityError::accepted`] tells
+/// how many of them were inserted before this one.
+pub struct CapacityError<K, V> {
+    /// The pair that was rejected.
+    pair: (K, V),
+    /// How many pairs were accepted before the rejected one.
+    accepted: usize,
+}
+
+/// A map with the same behavior as [`Map`], which keeps keys and values in two
+/// separate arrays.
+///
+/// When a key is searched for, only the array of keys is scanned, without
+/// touching the values at all. When the values are much larger than the keys,
+/// for example in `SplitMap<u32, [u8; 256], 16>`, a lookup in [`Map`] touches
+/// a new cache line for almost every key, while here many keys share the
+/// same cache line. This may help when the maps are not already in the cache.
+/// When they are, or when the values are small, [`Map`] is usually
+/// at least as fast, because the key and the value of a pair are located
+/// next to each other. The benchmarks in `benches/layout.rs` compare the two.
+///
+/// ```
+/// let mut m: micromap::SplitMap<u32, [u8; 256], 4> = micromap::SplitMap::new();
+/// m.insert(1, [42; 256]);
+/// m.insert(2, [16; 256]);
+/// assert_eq!(42, m[&1][0]);
+/// assert_eq!(vec![&1, &2], m.keys().collect::<Vec<_>>());
+/// ```
+pub struct SplitMap<K: PartialEq, V, const N: usize> {
+    /// The total number of pairs, which are all packed at the front of the arrays.
+    len: usize,
+    /// The fixed-size array of keys.
+    keys: [MaybeUninit<K>; N],
+    /// The fixed-size array of values, each at the same position as its key.
+    values: [MaybeUninit<V>; N],
+}
+
+/// Into-iterator over the [`SplitMap`].
+pub struct SplitIntoIter<K: PartialEq, V, const N: usize> {
+    pos: usize,
+    map: SplitMap<K, V, N>,
+}
+
+/// A map with the same behavior as [`Map`], which also keeps a one-byte
+/// fingerprint of every key, in a separate compact array.
+///
+/// The fingerprint is calculated from the hash of the key. When a key is
+/// searched for, the fingerprints are compared first, and the keys are
+/// compared only when their fingerprints match. This makes lookups much faster
+/// when comparing keys is expensive, for example with `String` keys that
+/// share long prefixes. For cheap keys, like integers, [`Map`] is faster,
+/// since it doesn't need to calculate any hashes.
+///
+/// ```
+/// let mut m: micromap::TaggedMap<String, u8, 16> = micromap::TaggedMap::new();
+/// m.insert("server.http.port".to_string(), 80);
+/// m.insert("server.https.port".to_string(), 43);
+/// assert_eq!(Some(&80), m.get("server.http.port"));
+/// ```
+pub struct TaggedMap<K: PartialEq, V, const N: usize> {
+    /// The fingerprints of the keys, at the same positions as the pairs.
+    tags: [u8; N],
+    /// The pairs.
+    map: Map<K, V, N>,
+}
+
+/// A map with a similar behavior as [`Map`], which keeps its pairs sorted
+/// by their keys.
+///
+/// Since the keys are sorted, they are found with a binary search, instead
+/// of the linear one, and the pairs are always iterated in the order of
+/// their keys, similar to how
+/// [`BTreeMap`](https://doc.rust-lang.org/std/collections/struct.BTreeMap.html)
+/// works. However, insertions and removals have to shift all pairs after
+/// the position of the key, which makes them slower than in [`Map`].
+///
+/// ```
+/// let mut m: micromap::SortedMap<i32, &str, 8> = micromap::SortedMap::new();
+/// m.insert(3, "three");
+/// m.insert(1, "one");
+/// m.insert(2, "two");
+/// assert_eq!(vec![&1, &2, &3], m.keys().collect::<Vec<_>>());
+/// assert_eq!(vec![(&2, &"two"), (&3, &"three")], m.range(2..).collect::<Vec<_>>());
+/// assert_eq!(Some((1, "one")), m.pop_first());
+/// ```
+pub struct SortedMap<K: Ord, V, const N: usize> {
+    /// The pairs, in the order of their keys.
+    map: Map<K, V, N>,
+}
+
+/// A set of unique values, which is a [`Map`] of keys without values.
+///
+/// ```
+/// let mut s: micromap::Set<&str, 8> = micromap::Set::new();
+/// assert!(s.insert("one"));
+/// assert!(s.insert("two"));
+/// assert!(!s.insert("one"));
+/// let t: micromap::Set<&str, 8> = ["two", "three"].into_iter().collect();
+/// assert_eq!(vec![&"two"], s.intersection(&t).collect::<Vec<_>>());
+/// assert_eq!(4, (&s | &t).len() + (&s & &t).len());
+/// ```
+pub struct Set<T: PartialEq, const N: usize> {
+    /// The values, as the keys of the map.
+    map: Map<T, (), N>,
+}
+
+/// An iterator over the values of the [`Set`].
+pub struct SetIter<'a, T: PartialEq, const N: usize> {
+    iter: Keys<'a, T, (), N>,
+}
+
+/// Consuming iterator over the values of the [`Set`].
+pub struct SetIntoIter<T: PartialEq, const N: usize> {
+    iter: IntoKeys<T, (), N>,
+}
+
+/// An iterator over the values in both of two [`Set`]s, made
+/// by [`Set::union`].
+pub struct Union<'a, T: PartialEq, const N: usize> {
+    iter: core::iter::Chain<SetIter<'a, T, N>, Difference<'a, T, N>>,
+}
+
+/// An iterator over the values present in two [`Set`]s at the same time,
+/// made by [`Set::intersection`].
+pub struct Intersection<'a, T: PartialEq, const N: usize> {
+    iter: SetIter<'a, T, N>,
+    other: &'a Set<T, N>,
+}
+
+/// An iterator over the values of one [`Set`], which are absent
+/// in another one, made by [`Set::difference`].
+pub struct Difference<'a, T: PartialEq, const N: usize> {
+    iter: SetIter<'a, T, N>,
+    other: &'a Set<T, N>,
+}
+
+/// An iterator over the values present in only one of two [`Set`]s,
+/// made by [`Set::symmetric_difference`].
+pub struct SymmetricDifference<'a, T: PartialEq, const N: usize> {
+    iter: core::iter::Chain<Difference<'a, T, N>, Difference<'a, T, N>>,
+}
+
+/// A map, which keeps up to `N` pairs inside, in a [`Map`], and moves them
+/// to the heap when there are more of them.
+///
+/// It is available only with the `alloc` feature. When the pairs are
+/// moved to the heap, they are kept in a [`Vec`], which
+/// grows as needed, and are still searched for one by one. The map never goes
+/// back to the inline storage by itself, but it may be asked to do so with
+/// [`SmallMap::shrink_to_inline`], when there are few enough pairs left.
+///
+/// ```
+/// let mut m: micromap::SmallMap<u32, u32, 2> = micromap::SmallMap::new();
+/// m.insert(1, 10);
+/// m.insert(2, 20);
+/// assert!(m.is_inline());
+/// m.insert(3, 30);
+/// assert!(!m.is_inline());
+/// assert_eq!(Some(&30), m.get(&3));
+/// m.remove(&3);
+/// assert!(m.shrink_to_inline());
+/// ```
+#[cfg(feature = "alloc")]
+pub struct SmallMap<K: PartialEq, V, const N: usize> {
+    /// The pairs, either inside or on the heap.
+    repr: small::Repr<K, V, N>,
+}
+
+/// An iterator over the pairs of the [`SmallMap`].
+#[cfg(feature = "alloc")]
+pub struct SmallIter<'a, K, V> {
+    iter: core::slice::Iter<'a, (K, V)>,
+}
+
+/// An iterator over the pairs of the [`SmallMap`], with mutable
+/// references to the values.
+#[cfg(feature = "alloc")]
+pub struct SmallIterMut<'a, K, V> {
+    iter: core::slice::IterMut<'a, (K, V)>,
+}
+
+/// Consuming iterator over the pairs of the [`SmallMap`].
+#[cfg(feature = "alloc")]
+pub struct SmallIntoIter<K: PartialEq, V, const N: usize> {
+    iter: small::IntoIterRepr<K, V, N>,
+}
+
+/// An iterator over the keys of the [`SmallMap`].
+#[cfg(feature = "alloc")]
+pub struct SmallKeys<'a, K, V> {
+    iter: SmallIter<'a, K, V>,
+}
+
+/// An iterator over the values of the [`SmallMap`].
+#[cfg(feature = "alloc")]
+pub struct SmallValues<'a, K, V> {
+    iter: SmallIter<'a, K, V>,
+}
@@ -0,0 +1,105 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Make a [`Map`](crate::Map) from a list of key-value pairs.
+///
+/// The capacity of the map may be given after a semicolon, otherwise it is
+/// inferred from the type the map is assigned to:
+///
+/// ```
+/// use micromap::{micromap, Map};
+/// let m = micromap! { "one" => 1, "two" => 2; capacity = 16 };
+/// assert_eq!(16, m.capacity());
+/// let m: Map<_, _, 4> = micromap! { "one" => 1, "two" => 2 };
+/// assert_eq!(Some(&2), m.get("two"));
+/// ```
+///
+/// The map is made by [`Map::from_array`](crate::Map::from_array), thus it
+/// works in `const` and `static` contexts, which is handy for lookup tables:
+///
+/// ```
+/// use micromap::{micromap, Map};
+/// static CODES: Map<u16, &str, 3> = micromap! { 200 => "OK", 404 => "Not Found" };
+/// assert_eq!(Some(&"OK"), CODES.get(&200));
+/// ```
+///
+/// More pairs than the capacity don't compile:
+///
+/// ```compile_fail
+/// use micromap::micromap;
+/// let m = micromap! { 1 => 1, 2 => 2, 3 => 3; capacity = 2 };
+/// ```
+///
+/// The keys are not compared with each other, so they all must be different.
+/// Duplicate keys produce an invalid map, see
+/// [`Map::from_array`](crate::Map::from_array).
+#[macro_export]
+macro_rules! micromap {
+    ($($k:expr => $v:expr),* $(,)? ; capacity = $n:expr $(,)?) => {
+        $crate::Map::<_, _, { $n }>::from_array([$(($k, $v)),*])
+    };
+    ($($k:expr => $v:expr),* $(,)?) => {
+        $crate::Map::from_array([$(($k, $v)),*])
+    };
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::Map;
+
+    static LETTERS: Map<char, u8, 3> = micromap! { 'a' => 1, 'b' => 2, 'c' => 3 };
+
+    #[test]
+    fn makes_map_with_capacity() {
+        let m = micromap! { 1 => "one", 2 => "two", ; capacity = 8 };
+        assert_eq!(8, m.capacity());
+        assert_eq!(2, m.len());
+        assert_eq!(Some(&"two"), m.get(&2));
+    }
+
+    #[test]
+    fn infers_capacity_from_type() {
+        let m: Map<&str, i32, 5> = micromap! { "x" => 1, "y" => -1 };
+        assert_eq!(5, m.capacity());
+        assert_eq!(Some(&-1), m.get("y"));
+    }
+
+    #[test]
+    fn makes_empty_map() {
+        let m: Map<u8, u8, 2> = micromap! {};
+        assert!(m.is_empty());
+        let mut m = micromap! { ; capacity = 3 };
+        m.insert(1, 1);
+        assert_eq!(1, m.len());
+    }
+
+    #[test]
+    fn makes_static_map() {
+        assert_eq!(Some(&2), LETTERS.get(&'b'));
+        assert_eq!(3, LETTERS.len());
+    }
+
+    #[test]
+    fn makes_const_map() {
+        const M: Map<u32, u32, 2> = micromap! { 7 => 49 };
+        assert_eq!(Some(&49), M.get(&7));
+    }
+}
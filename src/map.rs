@@ -18,12 +18,13 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Map;
+use crate::error::OVERFLOW;
+use crate::{CapacityError, KeyEq, Map};
 use core::borrow::Borrow;
 use core::mem;
-use core::mem::MaybeUninit;
+use core::ptr;
 
-impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
+impl<K: PartialEq, V, const N: usize, E> Map<K, V, N, E> {
     /// Get its total capacity.
     #[inline]
     #[must_use]
@@ -34,187 +35,386 @@ impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
     /// Is it empty?
     #[inline]
     #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
     }
 
     /// Return the total number of pairs inside.
+    ///
+    /// It is stored in the map, so it doesn't need to be calculated.
     #[inline]
     #[must_use]
-    pub fn len(&self) -> usize {
-        let mut busy = 0;
-        for i in 0..self.next {
-            if self.item(i).is_some() {
-                busy += 1;
-            }
-        }
-        busy
+    pub const fn len(&self) -> usize {
+        self.len
     }
 
     /// Does the map contain this key?
     #[inline]
     #[must_use]
-    pub fn contains_key(&self, k: &K) -> bool {
-        for i in 0..self.next {
-            if let Some((bk, _bv)) = self.item(i) {
-                if bk == k {
-                    return true;
-                }
-            }
-        }
-        false
+    pub fn contains_key<Q: ?Sized>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        E: KeyEq<Q>,
+    {
+        self.position(k).is_some()
     }
 
-    /// Remove by key.
+    /// Remove by key, returning the value if the key was previously in the map.
+    ///
+    /// The last pair in the map takes the place of the removed one, in order
+    /// to keep all pairs packed at the front of the array. If the order of pairs
+    /// must be preserved, use [`Map::shift_remove`] instead.
     #[inline]
-    pub fn remove(&mut self, k: &K) {
-        for i in 0..self.next {
-            if let Some(p) = self.item(i) {
-                if p.0.borrow() == k {
-                    self.pairs[i].write(None);
-                    break;
-                }
-            }
-        }
+    pub fn remove<Q: ?Sized>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        E: KeyEq<Q>,
+    {
+        self.remove_entry(k).map(|p| p.1)
     }
 
     /// Insert a single pair into the map.
     ///
+    /// If the map did not have this key present, `None` is returned.
+    /// If the map did have this key present, the value is updated, and
+    /// the old value is returned. The key is not updated.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// let mut m: micromap::Map<u8, &str, 4> = micromap::Map::new();
+    /// assert_eq!(None, m.insert(1, "one"));
+    /// assert_eq!(Some("one"), m.insert(1, "uno"));
+    /// ```
+    ///
     /// # Panics
     ///
-    /// It may panic if there are too many pairs in the map already. Pay attention,
-    /// it panics only in the "debug" mode. In the "release" mode, you are going to get
-    /// undefined behavior. This is done for the sake of performance, in order to
-    /// avoid a repetitive check for the boundary condition on every `insert()`.
+    /// If there are too many pairs in the map already. This check is done
+    /// in both "debug" and "release" modes. If you want to handle the overflow,
+    /// use [`Map::try_insert`] instead.
     #[inline]
-    pub fn insert(&mut self, k: K, v: V) {
-        let mut target = self.next;
-        let mut i = 0;
-        loop {
-            if i == self.next {
-                #[cfg(feature = "std")]
-                debug_assert!(target < N, "No more keys available in the map");
-                break;
-            }
-            match self.item(i) {
-                Some(p) => {
-                    if p.0 == k {
-                        target = i;
-                        unsafe {
-                            self.pairs[i].assume_init_drop();
-                        }
-                        break;
-                    }
-                }
-                None => {
-                    target = i;
-                }
+    pub fn insert(&mut self, k: K, v: V) -> Option<V>
+    where
+        E: KeyEq<K>,
+    {
+        if let Some(i) = self.position(&k) {
+            return Some(mem::replace(&mut self.item_mut(i).1, v));
+        }
+        assert!(self.len < N, "{OVERFLOW}");
+        self.push(k, v);
+        None
+    }
+
+    /// Try to insert a single pair into the map.
+    ///
+    /// If the map didn't have this key present, `Ok(None)` is returned.
+    /// If the map did have this key present, the value is updated, and
+    /// the old value is returned as `Ok(Some(old))`. The key is not updated.
+    ///
+    /// # Errors
+    ///
+    /// If the key is absent and there is no more space in the map,
+    /// a [`CapacityError`] is returned, with the rejected pair inside.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// let mut m: micromap::Map<u8, &str, 1> = micromap::Map::new();
+    /// assert_eq!(None, m.try_insert(1, "one").unwrap());
+    /// assert_eq!(Some("one"), m.try_insert(1, "uno").unwrap());
+    /// let e = m.try_insert(2, "two").unwrap_err();
+    /// assert_eq!((2, "two"), e.into_pair());
+    /// ```
+    #[inline]
+    pub fn try_insert(&mut self, k: K, v: V) -> Result<Option<V>, CapacityError<K, V>>
+    where
+        E: KeyEq<K>,
+    {
+        match self.position(&k) {
+            Some(i) => Ok(Some(mem::replace(&mut self.item_mut(i).1, v))),
+            None if self.len < N => {
+                self.push(k, v);
+                Ok(None)
             }
-            i += 1;
+            None => Err(CapacityError::new(k, v)),
         }
-        self.pairs[target].write(Some((k, v)));
-        if target == self.next {
-            self.next += 1;
+    }
+
+    /// Insert a single pair into the map, without checking the boundaries.
+    ///
+    /// If the map did have this key present, the value is updated, and
+    /// the old value is returned. The key is not updated.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that either the key is already in the map or
+    /// there is at least one free slot left. Otherwise, the pair will be
+    /// written outside of the array, which is undefined behavior. Only
+    /// the "debug" mode checks this condition.
+    #[inline]
+    pub unsafe fn insert_unchecked(&mut self, k: K, v: V) -> Option<V>
+    where
+        E: KeyEq<K>,
+    {
+        if let Some(i) = self.position(&k) {
+            return Some(mem::replace(&mut self.item_mut(i).1, v));
         }
+        debug_assert!(self.len < N, "{OVERFLOW}");
+        self.pairs.get_unchecked_mut(self.len).write((k, v));
+        self.len += 1;
+        None
     }
 
     /// Get a reference to a single value.
     #[inline]
     #[must_use]
-    pub fn get<Q: PartialEq + ?Sized>(&self, k: &Q) -> Option<&V>
+    pub fn get<Q: ?Sized>(&self, k: &Q) -> Option<&V>
     where
         K: Borrow<Q>,
+        E: KeyEq<Q>,
     {
-        for i in 0..self.next {
-            if let Some(p) = self.item(i) {
-                if p.0.borrow() == k {
-                    return Some(&p.1);
-                }
+        self.position(k).map(|i| &self.item(i).1)
+    }
+
+    /// Get a mutable reference to a single value.
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        E: KeyEq<Q>,
+    {
+        self.position(k).map(|i| &mut self.item_mut(i).1)
+    }
+
+    /// Get mutable references to many values at once.
+    ///
+    /// All keys are found in a single pass over the pairs. If any of the keys
+    /// is absent, or two of them are equal, `None` is returned.
+    ///
+    /// ```
+    /// let mut m: micromap::Map<&str, i32, 4> = micromap::Map::from([("alice", 10), ("bob", 5)]);
+    /// let [a, b] = m.get_many_mut(["alice", "bob"]).unwrap();
+    /// *a -= 3;
+    /// *b += 3;
+    /// assert_eq!(7, m["alice"]);
+    /// assert_eq!(8, m["bob"]);
+    /// assert!(m.get_many_mut(["alice", "alice"]).is_none());
+    /// assert!(m.get_many_mut(["alice", "carol"]).is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get_many_mut<Q: ?Sized, const M: usize>(&mut self, keys: [&Q; M]) -> Option<[&mut V; M]>
+    where
+        K: Borrow<Q>,
+        E: KeyEq<Q>,
+    {
+        let found = self.positions(keys)?;
+        for (j, i) in found.iter().enumerate() {
+            if found[..j].contains(i) {
+                return None;
             }
         }
-        None
+        Some(self.values_at(found))
     }
 
-    /// Get a mutable reference to a single value.
+    /// Get mutable references to many values at once, without checking
+    /// that the keys are different.
     ///
-    /// # Panics
+    /// If any of the keys is absent, `None` is returned.
     ///
-    /// If can't turn it into a mutable state.
+    /// # Safety
+    ///
+    /// Calling this method with equal keys is *[undefined behavior]*,
+    /// even if the references are not used.
+    ///
+    /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
     #[inline]
     #[must_use]
-    pub fn get_mut<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    pub unsafe fn get_many_unchecked_mut<Q: ?Sized, const M: usize>(
+        &mut self,
+        keys: [&Q; M],
+    ) -> Option<[&mut V; M]>
+    where
+        K: Borrow<Q>,
+        E: KeyEq<Q>,
+    {
+        self.positions(keys).map(|found| self.values_at(found))
+    }
+
+    /// Internal function to find the positions of many keys in a single pass,
+    /// returning `None` if any of them is absent.
+    #[inline]
+    fn positions<Q: ?Sized, const M: usize>(&self, keys: [&Q; M]) -> Option<[usize; M]>
     where
         K: Borrow<Q>,
+        E: KeyEq<Q>,
     {
-        for i in 0..self.next {
-            if let Some(p1) = self.item(i) {
-                if p1.0.borrow() == k {
-                    let p2 = unsafe { self.pairs[i].assume_init_mut() };
-                    return Some(&mut p2.as_mut().unwrap().1);
+        let mut found = [usize::MAX; M];
+        let mut left = M;
+        for i in 0..self.len {
+            if left == 0 {
+                break;
+            }
+            let k = self.item(i).0.borrow();
+            for (j, q) in keys.iter().enumerate() {
+                if found[j] == usize::MAX && E::eq(k, q) {
+                    found[j] = i;
+                    left -= 1;
                 }
             }
         }
-        None
+        if left == 0 {
+            Some(found)
+        } else {
+            None
+        }
+    }
+
+    /// Internal function to get mutable references to the values at the positions,
+    /// which the caller must guarantee to be different and occupied.
+    #[inline]
+    fn values_at<const M: usize>(&mut self, found: [usize; M]) -> [&mut V; M] {
+        let base = self.pairs.as_mut_ptr();
+        found.map(|i| unsafe { &mut (*base.add(i)).assume_init_mut().1 })
     }
 
     /// Remove all pairs from it, but keep the space intact for future use.
+    ///
+    /// All keys and values are dropped. If one of the destructors panics,
+    /// the rest of the pairs are still dropped and the map stays empty.
     #[inline]
     pub fn clear(&mut self) {
-        self.next = 0;
+        let busy = self.len;
+        self.len = 0;
+        unsafe {
+            let pairs = self.pairs.get_unchecked_mut(..busy);
+            ptr::drop_in_place(ptr::from_mut(pairs) as *mut [(K, V)]);
+        }
     }
 
-    /// Retains only the elements specified by the predicate.
+    /// Retains only the elements specified by the predicate, which may
+    /// also modify the values.
+    ///
+    /// The pairs that are not retained are dropped. The order of
+    /// the retained pairs doesn't change. The number of removed pairs
+    /// is returned.
+    ///
+    /// ```
+    /// let mut m: micromap::Map<u8, u8, 4> = micromap::Map::from([(1, 1), (2, 2), (3, 3)]);
+    /// let mut gone = vec![];
+    /// let removed = m.retain(|k, v| {
+    ///     *v *= 10;
+    ///     if *k == 2 {
+    ///         gone.push(*k);
+    ///     }
+    ///     *k != 2
+    /// });
+    /// assert_eq!(1, removed);
+    /// assert_eq!(vec![2], gone);
+    /// assert_eq!(30, m[&3]);
+    /// ```
+    ///
+    /// If the predicate or a destructor panics, the pairs not yet visited
+    /// stay in the map.
     #[inline]
-    pub fn retain<F: Fn(&K, &V) -> bool>(&mut self, f: F) {
-        for i in 0..self.next {
-            if let Some((k, v)) = self.item(i) {
-                if !f(k, v) {
-                    self.pairs[i].write(None);
-                }
-            }
-        }
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) -> usize {
+        self.extract_if(|k, v| !f(k, v)).count()
+    }
+
+    /// The same as [`Map::retain`], named after [`Vec::retain_mut`].
+    #[inline]
+    pub fn retain_mut<F: FnMut(&K, &mut V) -> bool>(&mut self, f: F) -> usize {
+        self.retain(f)
     }
 
-    /// Internal function to get access to the element in the internal array.
+    /// Internal function to get access to the pair in the internal array.
     #[inline]
-    const fn item(&self, i: usize) -> &Option<(K, V)> {
+    pub(crate) const fn item(&self, i: usize) -> &(K, V) {
         unsafe { self.pairs[i].assume_init_ref() }
     }
 
-    /// Returns the key-value pair corresponding to the supplied key.
+    /// Internal function to get mutable access to the pair in the internal array.
+    #[inline]
+    pub(crate) const fn item_mut(&mut self, i: usize) -> &mut (K, V) {
+        unsafe { self.pairs[i].assume_init_mut() }
+    }
+
+    /// Internal function to see all pairs as a slice.
     #[inline]
-    pub fn get_key_value<Q: PartialEq + ?Sized>(&self, k: &Q) -> Option<(&K, &V)>
+    pub(crate) const fn as_slice(&self) -> &[(K, V)] {
+        unsafe { core::slice::from_raw_parts(self.pairs.as_ptr().cast(), self.len) }
+    }
+
+    /// Internal function to see all pairs as a mutable slice.
+    #[inline]
+    pub(crate) const fn as_mut_slice(&mut self) -> &mut [(K, V)] {
+        unsafe { core::slice::from_raw_parts_mut(self.pairs.as_mut_ptr().cast(), self.len) }
+    }
+
+    /// Internal function to find the position of the key in the internal array.
+    #[inline]
+    pub(crate) fn position<Q: ?Sized>(&self, k: &Q) -> Option<usize>
     where
         K: Borrow<Q>,
+        E: KeyEq<Q>,
     {
-        for i in 0..self.next {
-            if let Some(p) = self.item(i) {
-                if p.0.borrow() == k {
-                    return Some((&p.0, &p.1));
-                }
+        #[cfg(feature = "simd")]
+        if E::NATIVE {
+            if let Some(i) = crate::simd::find_pair(&self.pairs[..self.len], k) {
+                return i;
             }
         }
+        let mut i = 0;
+        while i < self.len {
+            if E::eq(self.item(i).0.borrow(), k) {
+                return Some(i);
+            }
+            i += 1;
+        }
         None
     }
 
+    /// Internal function to put a new pair right after the last one.
+    ///
+    /// The caller must make sure there is space for it.
+    #[inline]
+    pub(crate) const fn push(&mut self, k: K, v: V) {
+        self.pairs[self.len].write((k, v));
+        self.len += 1;
+    }
+
+    /// Internal function to remove the pair from the internal array,
+    /// replacing it with the last one.
+    #[inline]
+    pub(crate) const fn swap_take(&mut self, i: usize) -> (K, V) {
+        self.len -= 1;
+        self.pairs.swap(i, self.len);
+        unsafe { self.pairs[self.len].assume_init_read() }
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[inline]
+    pub fn get_key_value<Q: ?Sized>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        E: KeyEq<Q>,
+    {
+        self.position(k).map(|i| {
+            let p = self.item(i);
+            (&p.0, &p.1)
+        })
+    }
+
     /// Removes a key from the map, returning the stored key and value if the
     /// key was previously in the map.
     #[inline]
-    pub fn remove_entry<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
+    pub fn remove_entry<Q: ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
     where
         K: Borrow<Q>,
+        E: KeyEq<Q>,
     {
-        for i in 0..self.next {
-            if let Some(p) = self.item(i) {
-                if p.0.borrow() == k {
-                    let ret = mem::replace(&mut self.pairs[i], MaybeUninit::new(None));
-                    unsafe {
-                        return ret.assume_init();
-                    }
-                }
-            }
-        }
-        None
+        self.position(k).map(|i| self.swap_take(i))
     }
 }
 
@@ -243,13 +443,80 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
-    #[cfg(debug_assertions)]
+    #[should_panic(expected = "No more keys available in the map")]
     fn cant_write_into_empty_map() {
         let mut m: Map<i32, i32, 0> = Map::new();
         m.insert(1, 42);
     }
 
+    #[test]
+    #[should_panic(expected = "No more keys available in the map")]
+    fn cant_write_into_full_map() {
+        let mut m: Map<i32, i32, 2> = Map::new();
+        m.insert(1, 42);
+        m.insert(2, 42);
+        m.insert(3, 42);
+    }
+
+    #[test]
+    fn try_insert_into_full_map() {
+        let mut m: Map<&str, i32, 2> = Map::new();
+        assert_eq!(None, m.try_insert("one", 1).unwrap());
+        assert_eq!(None, m.try_insert("two", 2).unwrap());
+        let e = m.try_insert("three", 3).unwrap_err();
+        assert_eq!(("three", 3), e.into_pair());
+        assert_eq!(2, m.len());
+        assert!(!m.contains_key(&"three"));
+    }
+
+    #[test]
+    fn try_insert_overwrites_in_full_map() {
+        let mut m: Map<&str, i32, 1> = Map::new();
+        m.try_insert("one", 1).unwrap();
+        assert_eq!(Some(1), m.try_insert("one", 2).unwrap());
+        assert_eq!(2, m["one"]);
+    }
+
+    #[test]
+    fn try_insert_into_empty_map() {
+        let mut m: Map<u8, u8, 0> = Map::new();
+        assert!(m.try_insert(1, 1).is_err());
+    }
+
+    #[test]
+    fn try_insert_after_remove() {
+        let mut m: Map<u8, u8, 2> = Map::new();
+        m.insert(1, 1);
+        m.insert(2, 2);
+        m.remove(&1);
+        assert_eq!(None, m.try_insert(3, 3).unwrap());
+        assert!(m.try_insert(4, 4).is_err());
+    }
+
+    #[test]
+    fn try_insert_drops_rejected_pair() {
+        use std::rc::Rc;
+        let mut m: Map<u8, Rc<()>, 1> = Map::new();
+        let v = Rc::new(());
+        m.insert(1, Rc::clone(&v));
+        let e = m.try_insert(2, Rc::clone(&v));
+        assert_eq!(Rc::strong_count(&v), 3);
+        drop(e);
+        assert_eq!(Rc::strong_count(&v), 2);
+    }
+
+    #[test]
+    fn inserts_unchecked() {
+        let mut m: Map<&str, i32, 2> = Map::new();
+        unsafe {
+            assert_eq!(None, m.insert_unchecked("one", 1));
+            assert_eq!(None, m.insert_unchecked("two", 2));
+            assert_eq!(Some(2), m.insert_unchecked("two", 3));
+        }
+        assert_eq!(2, m.len());
+        assert_eq!(3, m["two"]);
+    }
+
     #[test]
     fn empty_length() {
         let m: Map<u32, u32, 10> = Map::new();
@@ -312,6 +579,42 @@ mod test {
         assert!(m.get(&"one").is_none());
     }
 
+    #[test]
+    fn insert_returns_old_value() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        assert_eq!(None, m.insert("one", 42));
+        assert_eq!(Some(42), m.insert("one", 16));
+        assert_eq!(Some(16), m.insert("one", 8));
+        assert_eq!(8, m["one"]);
+    }
+
+    #[test]
+    fn remove_returns_value() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        m.insert("one", 42);
+        assert_eq!(Some(42), m.remove("one"));
+        assert_eq!(None, m.remove("one"));
+    }
+
+    #[test]
+    fn insert_keeps_old_key() {
+        let mut m: Map<String, i32, 10> = Map::new();
+        let k1 = "one".to_string();
+        let ptr = k1.as_ptr();
+        m.insert(k1, 1);
+        m.insert("one".to_string(), 2);
+        assert_eq!(ptr, m.keys().next().unwrap().as_ptr());
+    }
+
+    #[test]
+    fn removes_and_checks_by_borrowed_key() {
+        let mut m: Map<String, i32, 10> = Map::new();
+        m.insert("one".to_string(), 42);
+        assert!(m.contains_key("one"));
+        assert_eq!(Some(42), m.remove("one"));
+        assert!(!m.contains_key("one"));
+    }
+
     #[cfg(test)]
     #[derive(Clone)]
     struct Foo {
@@ -354,17 +657,71 @@ mod test {
         assert_eq!(0, m.len());
     }
 
+    #[test]
+    fn gets_many_values_mutably() {
+        let mut m: Map<u8, u8, 8> = (0..8).map(|i| (i, i)).collect();
+        let [a, b, c] = m.get_many_mut([&7, &0, &3]).unwrap();
+        *a += 10;
+        *b += 10;
+        *c += 10;
+        assert_eq!(
+            m.values().copied().collect::<Vec<_>>(),
+            [10, 1, 2, 13, 4, 5, 6, 17]
+        );
+        assert_eq!(Some([]), m.get_many_mut::<u8, 0>([]).map(|v| v.map(|x| *x)));
+    }
+
+    #[test]
+    fn rejects_duplicate_and_absent_keys() {
+        let mut m: Map<u8, u8, 8> = (0..8).map(|i| (i, i)).collect();
+        assert!(m.get_many_mut([&1, &2, &1]).is_none());
+        assert!(m.get_many_mut([&1, &8]).is_none());
+        let mut empty: Map<u8, u8, 1> = Map::new();
+        assert!(empty.get_many_mut([&1]).is_none());
+    }
+
+    #[test]
+    fn gets_many_values_unchecked() {
+        let mut m: Map<String, u8, 4> = Map::new();
+        m.insert("x".to_string(), 1);
+        m.insert("y".to_string(), 2);
+        let [x, y] = unsafe { m.get_many_unchecked_mut(["y", "x"]) }.unwrap();
+        std::mem::swap(x, y);
+        assert_eq!(m["x"], 2);
+        assert!(unsafe { m.get_many_unchecked_mut(["z"]) }.is_none());
+    }
+
     #[test]
     fn retain_test() {
         let vec: Vec<(i32, i32)> = (0..8).map(|x| (x, x * 10)).collect();
         let mut m: Map<i32, i32, 10> = Map::from_iter(vec);
         assert_eq!(m.len(), 8);
-        m.retain(|&k, _| k < 6);
+        assert_eq!(2, m.retain(|&k, _| k < 6));
         assert_eq!(m.len(), 6);
-        m.retain(|_, &v| v > 30);
+        assert_eq!(4, m.retain(|_, &mut v| v > 30));
         assert_eq!(m.len(), 2);
     }
 
+    #[test]
+    fn retain_with_state() {
+        let mut m: Map<u8, u8, 8> = Map::new();
+        for i in 0..8 {
+            m.insert(i, i);
+        }
+        let mut seen = 0;
+        let removed = m.retain_mut(|_, v| {
+            seen += 1;
+            *v += 100;
+            seen % 2 == 0
+        });
+        assert_eq!(4, removed);
+        assert_eq!(8, seen);
+        assert_eq!(
+            m.values().copied().collect::<Vec<_>>(),
+            [101, 103, 105, 107]
+        );
+    }
+
     #[test]
     fn insert_many_and_remove() {
         let mut m: Map<usize, u64, 4> = Map::new();
@@ -439,6 +796,39 @@ mod test {
         assert_eq!(Rc::strong_count(&v), 2);
     }
 
+    #[test]
+    fn keeps_pairs_packed_after_remove() {
+        let mut m: Map<u8, u8, 4> = Map::new();
+        for i in 0..4 {
+            m.insert(i, i);
+        }
+        m.remove(&1);
+        assert_eq!(3, m.len());
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [0, 3, 2]);
+        m.insert(4, 4);
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [0, 3, 2, 4]);
+    }
+
+    #[test]
+    fn retain_keeps_order() {
+        let mut m: Map<u8, u8, 8> = Map::new();
+        for i in 0..8 {
+            m.insert(i, i);
+        }
+        m.retain(|k, _| k % 3 != 0);
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [1, 2, 4, 5, 7]);
+        assert_eq!(5, m.len());
+    }
+
+    #[test]
+    fn has_no_space_for_options() {
+        use core::mem::size_of;
+        assert_eq!(
+            size_of::<usize>() + 4 * size_of::<(u64, u64)>(),
+            size_of::<Map<u64, u64, 4>>()
+        );
+    }
+
     #[test]
     fn insert_duplicate_after_remove() {
         let mut m: Map<_, _, 2> = Map::new();
@@ -0,0 +1,141 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::Map;
+use core::cmp::Ordering;
+
+impl<K: Ord, V, const N: usize> Map<K, V, N> {
+    /// Internal function to iterate over the pairs in the order of their keys,
+    /// without moving them.
+    #[inline]
+    fn sorted(&self) -> impl Iterator<Item = (&K, &V)> {
+        let mut order: [usize; N] = core::array::from_fn(|i| i);
+        order[..self.len].sort_unstable_by(|a, b| self.item(*a).0.cmp(&self.item(*b).0));
+        order.into_iter().take(self.len).map(|i| {
+            let p = self.item(i);
+            (&p.0, &p.1)
+        })
+    }
+}
+
+impl<K: Ord, V, const N: usize> Map<K, V, N> {
+    /// Compare it with another map lexicographically, by their pairs
+    /// sorted by keys, regardless of the order in which they were inserted.
+    ///
+    /// This is opt-in, since a [`Map`] doesn't implement [`PartialOrd`]: its
+    /// pairs are not ordered. The comparison is consistent with the equality:
+    /// two maps are equal if and only if it returns [`Ordering::Equal`].
+    /// For example:
+    ///
+    /// ```
+    /// use core::cmp::Ordering;
+    /// let a: micromap::Map<u8, u8, 4> = micromap::Map::from([(2, 0), (1, 9)]);
+    /// let b: micromap::Map<u8, u8, 4> = micromap::Map::from([(1, 9), (3, 0)]);
+    /// assert_eq!(Some(Ordering::Less), a.partial_cmp_by_sorted_keys(&b));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn partial_cmp_by_sorted_keys(&self, other: &Self) -> Option<Ordering>
+    where
+        V: PartialOrd,
+    {
+        self.sorted().partial_cmp(other.sorted())
+    }
+
+    /// Compare it with another map lexicographically, by their pairs
+    /// sorted by keys, which is handy for sorting maps:
+    ///
+    /// ```
+    /// let mut maps: Vec<micromap::Map<u8, u8, 2>> =
+    ///     vec![micromap::Map::from([(2, 0)]), micromap::Map::from([(1, 9)])];
+    /// maps.sort_by(micromap::Map::cmp_by_sorted_keys);
+    /// assert_eq!(Some(&9), maps[0].get(&1));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn cmp_by_sorted_keys(&self, other: &Self) -> Ordering
+    where
+        V: Ord,
+    {
+        self.sorted().cmp(other.sorted())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn compares_regardless_of_order() {
+        let a: Map<u8, u8, 4> = Map::from([(3, 30), (1, 10), (2, 20)]);
+        let b: Map<u8, u8, 4> = Map::from([(1, 10), (2, 20), (3, 30)]);
+        assert_eq!(Ordering::Equal, a.cmp_by_sorted_keys(&b));
+        let c: Map<u8, u8, 4> = Map::from([(1, 10), (2, 21)]);
+        assert_eq!(Ordering::Less, a.cmp_by_sorted_keys(&c));
+        let d: Map<u8, u8, 4> = Map::from([(1, 10), (2, 20)]);
+        assert_eq!(Ordering::Less, d.cmp_by_sorted_keys(&a));
+        assert_eq!(
+            Ordering::Less,
+            Map::<u8, u8, 4>::new().cmp_by_sorted_keys(&d)
+        );
+    }
+
+    #[test]
+    fn sorts_maps() {
+        let mut maps: Vec<Map<&str, u8, 2>> = vec![
+            Map::from([("b", 1)]),
+            Map::from([("a", 2), ("b", 1)]),
+            Map::from([("b", 1), ("a", 1)]),
+        ];
+        maps.sort_by(Map::cmp_by_sorted_keys);
+        assert_eq!(
+            vec![
+                vec![("a", 1), ("b", 1)],
+                vec![("a", 2), ("b", 1)],
+                vec![("b", 1)]
+            ],
+            maps.iter()
+                .map(|m| m.sorted().map(|(k, v)| (*k, *v)).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn agrees_with_equality() {
+        let maps: Vec<Map<u8, u8, 4>> = (0..81u8)
+            .map(|i| Map::from([(i % 3, i / 27), ((i / 3) % 3 + 3, (i / 9) % 3)]))
+            .collect();
+        for a in &maps {
+            for b in &maps {
+                assert_eq!(a == b, a.cmp_by_sorted_keys(b) == Ordering::Equal);
+                assert_eq!(a.cmp_by_sorted_keys(b), b.cmp_by_sorted_keys(a).reverse());
+            }
+        }
+    }
+
+    #[test]
+    fn compares_partially() {
+        let a: Map<u8, f64, 2> = Map::from([(1, f64::NAN)]);
+        let b: Map<u8, f64, 2> = Map::from([(1, 0.0)]);
+        assert_eq!(None, a.partial_cmp_by_sorted_keys(&b));
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,430 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::error::OVERFLOW;
+use crate::Map;
+use core::borrow::Borrow;
+use core::mem;
+use core::ptr;
+
+impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
+    /// Get the pair by its position in the map.
+    ///
+    /// Positions are assigned in the order of insertion. For example:
+    ///
+    /// ```
+    /// let mut m: micromap::Map<&str, i32, 4> = micromap::Map::new();
+    /// m.insert("one", 1);
+    /// m.insert("two", 2);
+    /// assert_eq!(Some((&"two", &2)), m.get_index(1));
+    /// assert_eq!(None, m.get_index(2));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn get_index(&self, i: usize) -> Option<(&K, &V)> {
+        if i < self.len {
+            let p = self.item(i);
+            Some((&p.0, &p.1))
+        } else {
+            None
+        }
+    }
+
+    /// Get the pair by its position in the map, with a mutable reference
+    /// to the value.
+    #[inline]
+    #[must_use]
+    pub const fn get_index_mut(&mut self, i: usize) -> Option<(&K, &mut V)> {
+        if i < self.len {
+            let p = self.item_mut(i);
+            Some((&p.0, &mut p.1))
+        } else {
+            None
+        }
+    }
+
+    /// Get the position of the key in the map, if it is there.
+    #[inline]
+    #[must_use]
+    pub fn get_index_of<Q: PartialEq + ?Sized>(&self, k: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k)
+    }
+
+    /// Get the position of the key, the key and the value, if the key is there.
+    #[inline]
+    #[must_use]
+    pub fn get_full<Q: PartialEq + ?Sized>(&self, k: &Q) -> Option<(usize, &K, &V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| {
+            let p = self.item(i);
+            (i, &p.0, &p.1)
+        })
+    }
+
+    /// Insert a single pair into the map and return its position together
+    /// with the old value, if the key was already in the map.
+    ///
+    /// If the key is new, it is placed at the end of the map. Otherwise,
+    /// the pair keeps its position.
+    ///
+    /// # Panics
+    ///
+    /// If there are too many pairs in the map already.
+    #[inline]
+    pub fn insert_full(&mut self, k: K, v: V) -> (usize, Option<V>) {
+        if let Some(i) = self.position(&k) {
+            return (i, Some(mem::replace(&mut self.item_mut(i).1, v)));
+        }
+        assert!(self.len < N, "{OVERFLOW}");
+        self.push(k, v);
+        (self.len - 1, None)
+    }
+
+    /// Get the first pair, which is the oldest one, if nothing was moved.
+    #[inline]
+    #[must_use]
+    pub const fn first(&self) -> Option<(&K, &V)> {
+        self.get_index(0)
+    }
+
+    /// Get the first pair, with a mutable reference to the value.
+    #[inline]
+    #[must_use]
+    pub const fn first_mut(&mut self) -> Option<(&K, &mut V)> {
+        self.get_index_mut(0)
+    }
+
+    /// Get the last pair, which is the most recently inserted one.
+    #[inline]
+    #[must_use]
+    pub const fn last(&self) -> Option<(&K, &V)> {
+        if self.len == 0 {
+            None
+        } else {
+            self.get_index(self.len - 1)
+        }
+    }
+
+    /// Get the last pair, with a mutable reference to the value.
+    #[inline]
+    #[must_use]
+    pub const fn last_mut(&mut self) -> Option<(&K, &mut V)> {
+        if self.len == 0 {
+            None
+        } else {
+            self.get_index_mut(self.len - 1)
+        }
+    }
+
+    /// Remove the last pair and return it.
+    ///
+    /// It takes constant time and doesn't disturb the order of other pairs.
+    #[inline]
+    pub const fn pop(&mut self) -> Option<(K, V)> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            Some(unsafe { self.pairs[self.len].assume_init_read() })
+        }
+    }
+
+    /// Remove the pair by key, replacing it with the last pair in the map.
+    ///
+    /// This is exactly what [`Map::remove`] does. It is fast, but the order
+    /// of the pairs is disturbed: the last pair takes the position of the
+    /// removed one.
+    #[inline]
+    pub fn swap_remove<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        self.swap_remove_entry(k).map(|p| p.1)
+    }
+
+    /// Remove the pair by key and return it, replacing it with the last pair in the map.
+    #[inline]
+    pub fn swap_remove_entry<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| self.swap_take(i))
+    }
+
+    /// Remove the pair by its position, replacing it with the last pair in the map.
+    #[inline]
+    pub const fn swap_remove_index(&mut self, i: usize) -> Option<(K, V)> {
+        if i < self.len {
+            Some(self.swap_take(i))
+        } else {
+            None
+        }
+    }
+
+    /// Remove the pair by key, shifting all pairs after it one position
+    /// to the front.
+    ///
+    /// It is slower than [`Map::swap_remove`], but the order of
+    /// the remaining pairs is preserved. For example:
+    ///
+    /// ```
+    /// let mut m: micromap::Map<u8, u8, 4> = micromap::Map::new();
+    /// m.insert(1, 1);
+    /// m.insert(2, 2);
+    /// m.insert(3, 3);
+    /// m.shift_remove(&1);
+    /// assert_eq!(vec![2, 3], m.into_keys().collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    pub fn shift_remove<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        self.shift_remove_entry(k).map(|p| p.1)
+    }
+
+    /// Remove the pair by key and return it, shifting all pairs after it
+    /// one position to the front.
+    #[inline]
+    pub fn shift_remove_entry<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| self.shift_take(i))
+    }
+
+    /// Remove the pair by its position, shifting all pairs after it
+    /// one position to the front.
+    #[inline]
+    pub const fn shift_remove_index(&mut self, i: usize) -> Option<(K, V)> {
+        if i < self.len {
+            Some(self.shift_take(i))
+        } else {
+            None
+        }
+    }
+
+    /// Move the pair from one position to another, shifting all pairs
+    /// in between.
+    ///
+    /// # Panics
+    ///
+    /// If any of the positions is out of bounds.
+    #[inline]
+    pub fn move_index(&mut self, from: usize, to: usize) {
+        assert!(from < self.len, "The position {from} is out of bounds");
+        assert!(to < self.len, "The position {to} is out of bounds");
+        if from < to {
+            self.pairs[from..=to].rotate_left(1);
+        } else {
+            self.pairs[to..=from].rotate_right(1);
+        }
+    }
+
+    /// Swap the positions of two pairs.
+    ///
+    /// # Panics
+    ///
+    /// If any of the positions is out of bounds.
+    #[inline]
+    pub fn swap_indices(&mut self, a: usize, b: usize) {
+        assert!(a < self.len, "The position {a} is out of bounds");
+        assert!(b < self.len, "The position {b} is out of bounds");
+        self.pairs.swap(a, b);
+    }
+
+    /// Internal function to remove the pair from the internal array,
+    /// shifting all pairs after it.
+    #[inline]
+    pub(crate) const fn shift_take(&mut self, i: usize) -> (K, V) {
+        unsafe {
+            let ret = self.pairs[i].assume_init_read();
+            let base = self.pairs.as_mut_ptr();
+            ptr::copy(base.add(i + 1), base.add(i), self.len - i - 1);
+            self.len -= 1;
+            ret
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn abcd() -> Map<char, u32, 8> {
+        let mut m = Map::new();
+        for (c, i) in ('a'..='d').zip(0..) {
+            m.insert(c, i);
+        }
+        m
+    }
+
+    fn keys(m: &Map<char, u32, 8>) -> String {
+        m.keys().collect()
+    }
+
+    #[test]
+    fn iterates_in_insertion_order() {
+        let mut m: Map<u32, u32, 16> = Map::new();
+        for i in (0..16).rev() {
+            m.insert(i, i);
+        }
+        assert_eq!(
+            m.keys().copied().collect::<Vec<_>>(),
+            (0..16).rev().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn keeps_position_on_overwrite() {
+        let mut m = abcd();
+        m.insert('b', 42);
+        assert_eq!("abcd", keys(&m));
+        assert_eq!(Some((&'b', &42)), m.get_index(1));
+    }
+
+    #[test]
+    fn gets_by_index() {
+        let mut m = abcd();
+        assert_eq!(Some((&'c', &2)), m.get_index(2));
+        assert_eq!(None, m.get_index(4));
+        *m.get_index_mut(2).unwrap().1 = 42;
+        assert_eq!(42, m[&'c']);
+        assert!(m.get_index_mut(4).is_none());
+    }
+
+    #[test]
+    fn finds_index_of_key() {
+        let m = abcd();
+        assert_eq!(Some(3), m.get_index_of(&'d'));
+        assert_eq!(None, m.get_index_of(&'z'));
+        assert_eq!(Some((1, &'b', &1)), m.get_full(&'b'));
+        assert_eq!(None, m.get_full(&'z'));
+    }
+
+    #[test]
+    fn inserts_full() {
+        let mut m = abcd();
+        assert_eq!((4, None), m.insert_full('e', 4));
+        assert_eq!((4, Some(4)), m.insert_full('e', 5));
+        assert_eq!((0, Some(0)), m.insert_full('a', 6));
+    }
+
+    #[test]
+    fn gets_first_and_last() {
+        let mut m = abcd();
+        assert_eq!(Some((&'a', &0)), m.first());
+        assert_eq!(Some((&'d', &3)), m.last());
+        *m.first_mut().unwrap().1 = 10;
+        *m.last_mut().unwrap().1 = 30;
+        assert_eq!(10, m[&'a']);
+        assert_eq!(30, m[&'d']);
+        let mut e: Map<char, u32, 8> = Map::new();
+        assert!(e.first().is_none());
+        assert!(e.last().is_none());
+        assert!(e.first_mut().is_none());
+        assert!(e.last_mut().is_none());
+    }
+
+    #[test]
+    fn pops_last_pair() {
+        let mut m = abcd();
+        assert_eq!(Some(('d', 3)), m.pop());
+        assert_eq!(Some(('c', 2)), m.pop());
+        assert_eq!("ab", keys(&m));
+        m.pop();
+        m.pop();
+        assert_eq!(None, m.pop());
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn swap_removes() {
+        let mut m = abcd();
+        assert_eq!(Some(0), m.swap_remove(&'a'));
+        assert_eq!("dbc", keys(&m));
+        assert_eq!(None, m.swap_remove(&'a'));
+        assert_eq!(Some(('b', 1)), m.swap_remove_entry(&'b'));
+        assert_eq!("dc", keys(&m));
+        assert_eq!(Some(('d', 3)), m.swap_remove_index(0));
+        assert_eq!(None, m.swap_remove_index(1));
+        assert_eq!("c", keys(&m));
+    }
+
+    #[test]
+    fn shift_removes() {
+        let mut m = abcd();
+        assert_eq!(Some(1), m.shift_remove(&'b'));
+        assert_eq!("acd", keys(&m));
+        assert_eq!(None, m.shift_remove(&'b'));
+        assert_eq!(Some(('a', 0)), m.shift_remove_entry(&'a'));
+        assert_eq!("cd", keys(&m));
+        assert_eq!(Some(('d', 3)), m.shift_remove_index(1));
+        assert_eq!(None, m.shift_remove_index(1));
+        assert_eq!("c", keys(&m));
+    }
+
+    #[test]
+    fn moves_index() {
+        let mut m = abcd();
+        m.move_index(0, 3);
+        assert_eq!("bcda", keys(&m));
+        m.move_index(3, 1);
+        assert_eq!("bacd", keys(&m));
+        m.move_index(2, 2);
+        assert_eq!("bacd", keys(&m));
+        assert_eq!(Some(3), m.get_index_of(&'d'));
+    }
+
+    #[test]
+    fn swaps_indices() {
+        let mut m = abcd();
+        m.swap_indices(0, 2);
+        assert_eq!("cbad", keys(&m));
+    }
+
+    #[test]
+    #[should_panic(expected = "The position 4 is out of bounds")]
+    fn cant_move_outside() {
+        let mut m = abcd();
+        m.move_index(4, 0);
+    }
+
+    #[test]
+    fn drops_shift_removed_pair() {
+        use std::rc::Rc;
+        let mut m: Map<u8, Rc<()>, 4> = Map::new();
+        let v = Rc::new(());
+        for i in 0..4 {
+            m.insert(i, Rc::clone(&v));
+        }
+        m.shift_remove(&1);
+        m.pop();
+        assert_eq!(3, Rc::strong_count(&v));
+        drop(m);
+        assert_eq!(1, Rc::strong_count(&v));
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Helper modules, which change the way a [`Map`] is serialized or
+//! deserialized, to be used with `#[serde(with = "...")]`.
+
+use crate::serialization::{Dups, Shape, Vi};
+use crate::Map;
+
+/// Serialize a [`Map`] as a sequence of `[key, value]` pairs, instead
+/// of a map, which works for any keys, not only for strings, in JSON.
+/// For example:
+///
+/// ```
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Grid {
+///     #[serde(with = "micromap::serde::as_pairs")]
+///     cells: micromap::Map<(u8, u8), char, 8>,
+/// }
+/// ```
+///
+/// Deserialization accepts both the sequence of pairs and the map,
+/// from the formats that describe themselves, like JSON.
+pub mod as_pairs {
+    use super::{Dups, Map, Shape, Vi};
+    use ::serde::ser::SerializeSeq;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serialize the map as a sequence of pairs.
+    ///
+    /// # Errors
+    ///
+    /// If the serializer fails.
+    #[inline]
+    pub fn serialize<K, V, S, const N: usize>(
+        m: &Map<K, V, N>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        K: PartialEq + Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(m.len()))?;
+        for p in m {
+            seq.serialize_element(&p)?;
+        }
+        seq.end()
+    }
+
+    /// Deserialize the map from a sequence of pairs, or from a map.
+    ///
+    /// # Errors
+    ///
+    /// If there are more pairs than the capacity of the map,
+    /// or if the deserializer fails.
+    #[inline]
+    pub fn deserialize<'de, K, V, D, const N: usize>(
+        deserializer: D,
+    ) -> Result<Map<K, V, N>, D::Error>
+    where
+        K: PartialEq + Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Vi::new(Dups::LastWins).deserialize(deserializer, Shape::Pairs)
+    }
+}
+
+/// Deserialize a [`Map`], rejecting duplicate keys with an error, instead
+/// of keeping the last value of each key. For example:
+///
+/// ```
+/// #[derive(serde::Deserialize)]
+/// struct Headers {
+///     #[serde(with = "micromap::serde::unique")]
+///     values: micromap::Map<String, String, 8>,
+/// }
+/// ```
+///
+/// Serialization is the same as without it.
+pub mod unique {
+    use super::{Dups, Map, Shape, Vi};
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serialize the map as usual.
+    ///
+    /// # Errors
+    ///
+    /// If the serializer fails.
+    #[inline]
+    pub fn serialize<K, V, S, const N: usize>(
+        m: &Map<K, V, N>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        K: PartialEq + Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        m.serialize(serializer)
+    }
+
+    /// Deserialize the map, rejecting duplicate keys.
+    ///
+    /// # Errors
+    ///
+    /// If a key shows up twice, if there are more pairs than the capacity
+    /// of the map, or if the deserializer fails.
+    #[inline]
+    pub fn deserialize<'de, K, V, D, const N: usize>(
+        deserializer: D,
+    ) -> Result<Map<K, V, N>, D::Error>
+    where
+        K: PartialEq + Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Vi::new(Dups::Reject).deserialize(deserializer, Shape::Map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use ::serde::de::value::{Error, MapDeserializer};
+
+    fn parse(pairs: &[(u8, u8)]) -> Result<Map<u8, u8, 4>, Error> {
+        unique::deserialize(MapDeserializer::new(pairs.iter().copied()))
+    }
+
+    #[test]
+    fn rejects_duplicate_keys() {
+        let e = parse(&[(1, 1), (2, 2), (1, 3)]).unwrap_err();
+        assert_eq!("duplicate key in a Map", e.to_string());
+    }
+
+    #[test]
+    fn accepts_unique_keys() {
+        let m = parse(&[(1, 1), (2, 2)]).unwrap();
+        assert_eq!(2, m.len());
+        assert!(parse(&[(1, 1), (2, 2), (3, 3), (4, 4), (5, 5)]).is_err());
+    }
+
+    #[test]
+    fn rejects_duplicate_keys_in_json() {
+        let mut de = serde_json::Deserializer::from_str(r#"{"a": 1, "a": 2}"#);
+        assert!(unique::deserialize::<String, u8, _, 2>(&mut de).is_err());
+    }
+
+    #[test]
+    fn serializes_pairs_to_json() {
+        let m: Map<(u8, u8), char, 4> = Map::from([((0, 1), 'x'), ((2, 3), 'y')]);
+        let mut json = vec![];
+        as_pairs::serialize(&m, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        assert_eq!(
+            r#"[[[0,1],"x"],[[2,3],"y"]]"#,
+            String::from_utf8(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn serializes_as_usual() {
+        let m: Map<u8, u8, 4> = Map::from([(1, 2)]);
+        let mut bytes = vec![];
+        unique::serialize(
+            &m,
+            &mut bincode::Serializer::new(&mut bytes, bincode::DefaultOptions::new()),
+        )
+        .unwrap();
+        assert!(!bytes.is_empty());
+    }
+}
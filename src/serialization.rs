@@ -18,11 +18,11 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Map;
+use crate::{Map, Set, SortedMap};
 use core::fmt::Formatter;
 use core::marker::PhantomData;
-use serde::de::{MapAccess, Visitor};
-use serde::ser::SerializeMap;
+use serde::de::{Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 impl<K: PartialEq + Serialize, V: Serialize, const N: usize> Serialize for Map<K, V, N> {
@@ -31,14 +31,79 @@ impl<K: PartialEq + Serialize, V: Serialize, const N: usize> Serialize for Map<K
         S: Serializer,
     {
         let mut map = serializer.serialize_map(Some(self.len()))?;
-        for (a, v) in self.iter() {
+        for (a, v) in self {
             map.serialize_entry(a, v)?;
         }
         map.end()
     }
 }
 
-struct Vi<K, V, const N: usize>(PhantomData<K>, PhantomData<V>);
+/// The form in which a [`Map`] is expected by [`Vi::deserialize`].
+#[derive(Clone, Copy)]
+pub enum Shape {
+    /// A map of keys to values.
+    Map,
+    /// A sequence of `[key, value]` pairs.
+    Pairs,
+}
+
+/// What [`Vi`] does when a key shows up more than once.
+#[derive(Clone, Copy)]
+pub enum Dups {
+    /// Keep the last value of the key.
+    LastWins,
+    /// Fail with an error.
+    Reject,
+}
+
+/// The visitor of a [`Map`], which never takes more pairs than the map can hold.
+pub struct Vi<K, V, const N: usize> {
+    /// What to do with duplicate keys.
+    dups: Dups,
+    /// The type of the pairs.
+    pairs: PhantomData<(K, V)>,
+}
+
+impl<K, V, const N: usize> Vi<K, V, N> {
+    /// Make it, treating duplicate keys as told.
+    pub const fn new(dups: Dups) -> Self {
+        Self {
+            dups,
+            pairs: PhantomData,
+        }
+    }
+}
+
+impl<'de, K: PartialEq + Deserialize<'de>, V: Deserialize<'de>, const N: usize> Vi<K, V, N> {
+    /// Deserialize the map, which is expected in the given [`Shape`].
+    ///
+    /// Human-readable formats, like JSON, describe themselves, so both
+    /// shapes are accepted from them, no matter which one is expected.
+    pub fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+        shape: Shape,
+    ) -> Result<Map<K, V, N>, D::Error> {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(self);
+        }
+        match shape {
+            Shape::Map => deserializer.deserialize_map(self),
+            Shape::Pairs => deserializer.deserialize_seq(self),
+        }
+    }
+
+    /// Put one more pair into the map, which is the `seen`-th one.
+    fn take<E: Error>(&self, m: &mut Map<K, V, N>, seen: usize, k: K, v: V) -> Result<(), E> {
+        match m.try_insert(k, v) {
+            Ok(Some(_)) if matches!(self.dups, Dups::Reject) => {
+                Err(E::custom("duplicate key in a Map"))
+            }
+            Ok(_) => Ok(()),
+            Err(_) => Err(E::invalid_length(seen, self)),
+        }
+    }
+}
 
 impl<'de, K: PartialEq + Deserialize<'de>, V: Deserialize<'de>, const N: usize> Visitor<'de>
     for Vi<K, V, N>
@@ -46,16 +111,46 @@ impl<'de, K: PartialEq + Deserialize<'de>, V: Deserialize<'de>, const N: usize>
     type Value = Map<K, V, N>;
 
     fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
-        formatter.write_str("a Map")
+        write!(formatter, "a Map with at most {N} pairs")
     }
 
+    /// Take all pairs, failing with `invalid_length` as soon as there are
+    /// more of them than the map can hold. If the format knows the number
+    /// of pairs upfront, it is checked before reading any of them, even
+    /// though some of the keys may be duplicates.
     fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
     where
         M: MapAccess<'de>,
     {
+        if let Some(n) = access.size_hint() {
+            if n > N {
+                return Err(M::Error::invalid_length(n, &self));
+            }
+        }
         let mut m: Self::Value = Map::new();
+        let mut seen = 0;
         while let Some((key, value)) = access.next_entry()? {
-            m.insert(key, value);
+            seen += 1;
+            self.take(&mut m, seen, key, value)?;
+        }
+        Ok(m)
+    }
+
+    /// Take all pairs from a sequence of them, just like from a map.
+    fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        if let Some(n) = access.size_hint() {
+            if n > N {
+                return Err(A::Error::invalid_length(n, &self));
+            }
+        }
+        let mut m: Self::Value = Map::new();
+        let mut seen = 0;
+        while let Some((key, value)) = access.next_element()? {
+            seen += 1;
+            self.take(&mut m, seen, key, value)?;
         }
         Ok(m)
     }
@@ -68,7 +163,79 @@ impl<'de, K: PartialEq + Deserialize<'de>, V: Deserialize<'de>, const N: usize>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_map(Vi(PhantomData, PhantomData))
+        Vi::new(Dups::LastWins).deserialize(deserializer, Shape::Map)
+    }
+}
+
+impl<K: Ord + Serialize, V: Serialize, const N: usize> Serialize for SortedMap<K, V, N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.map.serialize(serializer)
+    }
+}
+
+impl<'de, K: Ord + Deserialize<'de>, V: Deserialize<'de>, const N: usize> Deserialize<'de>
+    for SortedMap<K, V, N>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Map::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl<T: PartialEq + Serialize, const N: usize> Serialize for Set<T, N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for v in self {
+            seq.serialize_element(v)?;
+        }
+        seq.end()
+    }
+}
+
+struct SetVi<T, const N: usize>(PhantomData<T>);
+
+impl<'de, T: PartialEq + Deserialize<'de>, const N: usize> Visitor<'de> for SetVi<T, N> {
+    type Value = Set<T, N>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        write!(formatter, "a Set with at most {N} values")
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        if let Some(n) = access.size_hint() {
+            if n > N {
+                return Err(A::Error::invalid_length(n, &self));
+            }
+        }
+        let mut s: Self::Value = Set::new();
+        let mut seen = 0;
+        while let Some(v) = access.next_element()? {
+            seen += 1;
+            if s.map.try_insert(v, ()).is_err() {
+                return Err(A::Error::invalid_length(seen, &self));
+            }
+        }
+        Ok(s)
+    }
+}
+
+impl<'de, T: PartialEq + Deserialize<'de>, const N: usize> Deserialize<'de> for Set<T, N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SetVi(PhantomData))
     }
 }
 
@@ -91,3 +258,77 @@ fn empty_map_serde() {
     let after: Map<u8, u8, 8> = deserialize(&bytes).unwrap();
     assert!(after.is_empty());
 }
+
+#[test]
+fn sorted_map_serde() {
+    let mut before: SortedMap<u8, u8, 8> = SortedMap::new();
+    before.insert(3, 30);
+    before.insert(1, 10);
+    let bytes: Vec<u8> = serialize(&before).unwrap();
+    let after: SortedMap<u8, u8, 8> = deserialize(&bytes).unwrap();
+    assert_eq!(before, after);
+    let unsorted: Map<u8, u8, 8> = [(5, 50), (2, 20)].into_iter().collect();
+    let after: SortedMap<u8, u8, 8> = deserialize(&serialize(&unsorted).unwrap()).unwrap();
+    assert_eq!(vec![&2, &5], after.keys().collect::<Vec<_>>());
+}
+
+#[test]
+fn set_serde() {
+    let before: Set<u8, 8> = [1, 2, 3].into_iter().collect();
+    let bytes: Vec<u8> = serialize(&before).unwrap();
+    let after: Set<u8, 8> = deserialize(&bytes).unwrap();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn rejects_too_many_pairs_upfront() {
+    let before: Map<u8, u8, 8> = (0..5).map(|i| (i, i)).collect();
+    let bytes: Vec<u8> = serialize(&before).unwrap();
+    let e = deserialize::<Map<u8, u8, 4>>(&bytes).unwrap_err();
+    assert_eq!(
+        "invalid length 5, expected a Map with at most 4 pairs",
+        e.to_string()
+    );
+}
+
+#[test]
+fn rejects_too_many_pairs_without_size_hint() {
+    use serde::de::value::{Error, MapDeserializer};
+    let pairs = (0..5u8).map(|i| (i, i)).filter(|_| true);
+    let e = Map::<u8, u8, 3>::deserialize(MapDeserializer::<_, Error>::new(pairs)).unwrap_err();
+    assert_eq!(
+        "invalid length 4, expected a Map with at most 3 pairs",
+        e.to_string()
+    );
+}
+
+#[test]
+fn keeps_last_of_duplicates() {
+    use serde::de::value::{Error, MapDeserializer};
+    let pairs = [(1u8, 1u8), (1, 2), (2, 3)].into_iter().filter(|_| true);
+    let m = Map::<u8, u8, 2>::deserialize(MapDeserializer::<_, Error>::new(pairs)).unwrap();
+    assert_eq!(2, m[&1]);
+}
+
+#[test]
+fn rejects_too_many_values_in_set() {
+    let bytes: Vec<u8> = serialize(&vec![1u8, 2, 3]).unwrap();
+    assert!(deserialize::<Set<u8, 2>>(&bytes).is_err());
+    assert_eq!(3, deserialize::<Set<u8, 3>>(&bytes).unwrap().len());
+}
+
+#[test]
+fn json_roundtrip() {
+    let before: Map<String, u8, 4> = Map::from([("one".to_string(), 1), ("two".to_string(), 2)]);
+    let json = serde_json::to_string(&before).unwrap();
+    assert_eq!(r#"{"one":1,"two":2}"#, json);
+    let after: Map<String, u8, 4> = serde_json::from_str(&json).unwrap();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn accepts_pairs_in_json() {
+    let m: Map<u8, bool, 4> = serde_json::from_str("[[1, true], [2, false]]").unwrap();
+    assert_eq!(Some(&false), m.get(&2));
+    assert!(serde_json::from_str::<Map<u8, bool, 1>>("[[1, true], [2, false]]").is_err());
+}
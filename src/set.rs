@@ -0,0 +1,685 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::error::OVERFLOW;
+use crate::{Difference, Intersection, Map, Set, SetIntoIter, SetIter, SymmetricDifference, Union};
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::{BitAnd, BitOr, BitXor, Sub};
+
+impl<T: PartialEq, const N: usize> Set<T, N> {
+    /// Make it.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { map: Map::new() }
+    }
+
+    /// Get its total capacity.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Is it empty?
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Return the total number of values inside.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Remove all values from it, but keep the space intact for future use.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Add a value to the set.
+    ///
+    /// Returns whether the value was newly inserted. If the set already
+    /// contained an equal value, it is not updated.
+    ///
+    /// # Panics
+    ///
+    /// If there are too many values in the set already.
+    #[inline]
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    /// Adds a value to the set, replacing the existing equal value, if any,
+    /// which is returned.
+    ///
+    /// # Panics
+    ///
+    /// If there are too many values in the set already.
+    #[inline]
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        if let Some(i) = self.map.position(&value) {
+            return Some(mem::replace(&mut self.map.item_mut(i).0, value));
+        }
+        assert!(self.map.len() < N, "{OVERFLOW}");
+        self.map.push(value, ());
+        None
+    }
+
+    /// Does the set contain this value?
+    #[inline]
+    #[must_use]
+    pub fn contains<Q: PartialEq + ?Sized>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Get a reference to the value in the set, which is equal to the given one.
+    #[inline]
+    #[must_use]
+    pub fn get<Q: PartialEq + ?Sized>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+    {
+        self.map.get_key_value(value).map(|p| p.0)
+    }
+
+    /// Remove a value from the set, returning whether it was present.
+    #[inline]
+    pub fn remove<Q: PartialEq + ?Sized>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    /// Remove a value from the set and return it, if it was present.
+    #[inline]
+    pub fn take<Q: PartialEq + ?Sized>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+    {
+        self.map.remove_entry(value).map(|p| p.0)
+    }
+
+    /// Retains only the values specified by the predicate, returning
+    /// the number of removed values.
+    #[inline]
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> usize {
+        self.map.retain(|k, ()| f(k))
+    }
+
+    /// Make an iterator over all values.
+    #[inline]
+    #[must_use]
+    pub const fn iter(&self) -> SetIter<'_, T, N> {
+        SetIter {
+            iter: self.map.keys(),
+        }
+    }
+
+    /// Make an iterator over the values that are in this set or in
+    /// the other one, without duplicates.
+    #[inline]
+    #[must_use]
+    pub fn union<'a>(&'a self, other: &'a Self) -> Union<'a, T, N> {
+        Union {
+            iter: self.iter().chain(other.difference(self)),
+        }
+    }
+
+    /// Make an iterator over the values that are both in this set and
+    /// in the other one.
+    #[inline]
+    #[must_use]
+    pub const fn intersection<'a>(&'a self, other: &'a Self) -> Intersection<'a, T, N> {
+        Intersection {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Make an iterator over the values that are in this set, but not
+    /// in the other one.
+    #[inline]
+    #[must_use]
+    pub const fn difference<'a>(&'a self, other: &'a Self) -> Difference<'a, T, N> {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Make an iterator over the values that are in only one of the two sets.
+    #[inline]
+    #[must_use]
+    pub fn symmetric_difference<'a>(&'a self, other: &'a Self) -> SymmetricDifference<'a, T, N> {
+        SymmetricDifference {
+            iter: self.difference(other).chain(other.difference(self)),
+        }
+    }
+
+    /// Are all values of this set also in the other one?
+    #[inline]
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.len() <= other.len() && self.iter().all(|v| other.contains(v))
+    }
+
+    /// Are all values of the other set also in this one?
+    #[inline]
+    #[must_use]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Do the two sets have no values in common?
+    #[inline]
+    #[must_use]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.iter().all(|v| !other.contains(v))
+    }
+}
+
+impl<T: PartialEq, const N: usize> Default for Set<T, N> {
+    /// Make a default empty [`Set`].
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq + Clone, const N: usize> Clone for Set<T, N> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for Set<T, N> {
+    /// Two sets are equal if they contain the same values, in any order.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.is_subset(other)
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for Set<T, N> {}
+
+impl<T: PartialEq + Debug, const N: usize> Debug for Set<T, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> FromIterator<T> for Set<T, N> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut s: Self = Self::new();
+        s.extend(iter);
+        s
+    }
+}
+
+impl<T: PartialEq, const N: usize> Extend<T> for Set<T, N> {
+    /// Insert all values, skipping the ones already in the set.
+    ///
+    /// # Panics
+    ///
+    /// If there is not enough space in the set for them.
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for v in iter {
+            self.insert(v);
+        }
+    }
+}
+
+impl<'a, T: PartialEq + Copy + 'a, const N: usize> Extend<&'a T> for Set<T, N> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<'a, T: PartialEq, const N: usize> Iterator for SetIter<'a, T, N> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T: PartialEq, const N: usize> DoubleEndedIterator for SetIter<'_, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<T: PartialEq, const N: usize> ExactSizeIterator for SetIter<'_, T, N> {}
+
+impl<T: PartialEq, const N: usize> FusedIterator for SetIter<'_, T, N> {}
+
+impl<T: PartialEq, const N: usize> Clone for SetIter<'_, T, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq + Debug, const N: usize> Debug for SetIter<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> Iterator for SetIntoIter<T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T: PartialEq, const N: usize> DoubleEndedIterator for SetIntoIter<T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<T: PartialEq, const N: usize> ExactSizeIterator for SetIntoIter<T, N> {}
+
+impl<T: PartialEq, const N: usize> FusedIterator for SetIntoIter<T, N> {}
+
+impl<T: PartialEq + Debug, const N: usize> Debug for SetIntoIter<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.iter.fmt(f)
+    }
+}
+
+impl<'a, T: PartialEq, const N: usize> Iterator for Union<'a, T, N> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T: PartialEq, const N: usize> FusedIterator for Union<'_, T, N> {}
+
+impl<T: PartialEq, const N: usize> Clone for Union<'_, T, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq + Debug, const N: usize> Debug for Union<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T: PartialEq, const N: usize> Iterator for Intersection<'a, T, N> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.find(|v| other.contains(*v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<T: PartialEq, const N: usize> FusedIterator for Intersection<'_, T, N> {}
+
+impl<T: PartialEq, const N: usize> Clone for Intersection<'_, T, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            other: self.other,
+        }
+    }
+}
+
+impl<T: PartialEq + Debug, const N: usize> Debug for Intersection<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T: PartialEq, const N: usize> Iterator for Difference<'a, T, N> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.find(|v| !other.contains(*v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<T: PartialEq, const N: usize> FusedIterator for Difference<'_, T, N> {}
+
+impl<T: PartialEq, const N: usize> Clone for Difference<'_, T, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            other: self.other,
+        }
+    }
+}
+
+impl<T: PartialEq + Debug, const N: usize> Debug for Difference<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T: PartialEq, const N: usize> Iterator for SymmetricDifference<'a, T, N> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T: PartialEq, const N: usize> FusedIterator for SymmetricDifference<'_, T, N> {}
+
+impl<T: PartialEq, const N: usize> Clone for SymmetricDifference<'_, T, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq + Debug, const N: usize> Debug for SymmetricDifference<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T: PartialEq, const N: usize> IntoIterator for &'a Set<T, N> {
+    type Item = &'a T;
+    type IntoIter = SetIter<'a, T, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: PartialEq, const N: usize> IntoIterator for Set<T, N> {
+    type Item = T;
+    type IntoIter = SetIntoIter<T, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        SetIntoIter {
+            iter: self.map.into_keys(),
+        }
+    }
+}
+
+impl<T: PartialEq + Clone, const N: usize> BitOr<&Set<T, N>> for &Set<T, N> {
+    type Output = Set<T, N>;
+
+    /// Make a new set with the values of both sets.
+    ///
+    /// # Panics
+    ///
+    /// If there are more than `N` values in both sets together.
+    #[inline]
+    fn bitor(self, rhs: &Set<T, N>) -> Set<T, N> {
+        self.union(rhs).cloned().collect()
+    }
+}
+
+impl<T: PartialEq + Clone, const N: usize> BitAnd<&Set<T, N>> for &Set<T, N> {
+    type Output = Set<T, N>;
+
+    /// Make a new set with the values present in both sets.
+    #[inline]
+    fn bitand(self, rhs: &Set<T, N>) -> Set<T, N> {
+        self.intersection(rhs).cloned().collect()
+    }
+}
+
+impl<T: PartialEq + Clone, const N: usize> Sub<&Set<T, N>> for &Set<T, N> {
+    type Output = Set<T, N>;
+
+    /// Make a new set with the values of the left set, which are
+    /// absent in the right one.
+    #[inline]
+    fn sub(self, rhs: &Set<T, N>) -> Set<T, N> {
+        self.difference(rhs).cloned().collect()
+    }
+}
+
+impl<T: PartialEq + Clone, const N: usize> BitXor<&Set<T, N>> for &Set<T, N> {
+    type Output = Set<T, N>;
+
+    /// Make a new set with the values present in only one of the sets.
+    ///
+    /// # Panics
+    ///
+    /// If there are more than `N` such values.
+    #[inline]
+    fn bitxor(self, rhs: &Set<T, N>) -> Set<T, N> {
+        self.symmetric_difference(rhs).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn set(values: &[i32]) -> Set<i32, 8> {
+        values.iter().copied().collect()
+    }
+
+    fn sorted<'a>(iter: impl Iterator<Item = &'a i32>) -> Vec<i32> {
+        let mut v: Vec<i32> = iter.copied().collect();
+        v.sort_unstable();
+        v
+    }
+
+    #[test]
+    fn inserts_and_removes() {
+        let mut s: Set<String, 4> = Set::new();
+        assert!(s.insert("one".to_string()));
+        assert!(!s.insert("one".to_string()));
+        assert!(s.contains("one"));
+        assert_eq!(1, s.len());
+        assert!(!s.remove("two"));
+        assert!(s.remove("one"));
+        assert!(s.is_empty());
+        s.insert("three".to_string());
+        assert_eq!(Some("three".to_string()), s.take("three"));
+        assert_eq!(None, s.take("three"));
+    }
+
+    #[test]
+    fn replaces_equal_value() {
+        #[derive(Debug)]
+        struct Named(i32, &'static str);
+        impl PartialEq for Named {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        let mut s: Set<Named, 4> = Set::new();
+        assert_eq!(None, s.replace(Named(1, "first")));
+        assert!(!s.insert(Named(1, "second")));
+        assert_eq!("first", s.get(&Named(1, "")).unwrap().1);
+        assert_eq!("first", s.replace(Named(1, "third")).unwrap().1);
+        assert_eq!("third", s.get(&Named(1, "")).unwrap().1);
+        assert_eq!(1, s.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "No more keys available in the map")]
+    fn replaces_nothing_in_full_set() {
+        let mut s: Set<i32, 2> = [1, 2].into_iter().collect();
+        assert_eq!(Some(2), s.replace(2));
+        s.replace(5);
+    }
+
+    #[test]
+    fn makes_set_algebra() {
+        let a = set(&[1, 2, 3, 4]);
+        let b = set(&[3, 4, 5]);
+        assert_eq!(vec![1, 2, 3, 4, 5], sorted(a.union(&b)));
+        assert_eq!(vec![3, 4], sorted(a.intersection(&b)));
+        assert_eq!(vec![1, 2], sorted(a.difference(&b)));
+        assert_eq!(vec![5], sorted(b.difference(&a)));
+        assert_eq!(vec![1, 2, 5], sorted(a.symmetric_difference(&b)));
+    }
+
+    #[test]
+    fn applies_operators() {
+        let a = set(&[1, 2, 3]);
+        let b = set(&[2, 3, 4]);
+        assert_eq!(set(&[1, 2, 3, 4]), &a | &b);
+        assert_eq!(set(&[2, 3]), &a & &b);
+        assert_eq!(set(&[1]), &a - &b);
+        assert_eq!(set(&[1, 4]), &a ^ &b);
+    }
+
+    #[test]
+    #[should_panic(expected = "No more keys available in the map")]
+    fn panics_on_too_big_union() {
+        let a: Set<i32, 2> = [1, 2].into_iter().collect();
+        let b: Set<i32, 2> = [3, 4].into_iter().collect();
+        let _ = &a | &b;
+    }
+
+    #[test]
+    fn compares_sets() {
+        let a = set(&[1, 2]);
+        let b = set(&[1, 2, 3]);
+        assert!(a.is_subset(&b));
+        assert!(!b.is_subset(&a));
+        assert!(b.is_superset(&a));
+        assert!(a.is_disjoint(&set(&[3, 4])));
+        assert!(!a.is_disjoint(&b));
+        assert_eq!(set(&[2, 1]), a);
+        assert_ne!(a, b);
+        assert_ne!(set(&[1, 5]), set(&[1, 2]));
+    }
+
+    #[test]
+    fn debugs_set() {
+        assert_eq!("{1, 2}", format!("{:?}", set(&[1, 2])));
+    }
+
+    #[test]
+    fn extends_and_iterates() {
+        let mut s = set(&[1]);
+        s.extend([2, 3]);
+        s.extend(&[3, 4]);
+        assert_eq!(1, s.retain(|v| *v != 2));
+        assert_eq!(vec![1, 3, 4], s.iter().copied().collect::<Vec<_>>());
+        assert_eq!(vec![1, 3, 4], s.clone().into_iter().collect::<Vec<_>>());
+        assert_eq!(vec![&1, &3, &4], (&s).into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn iterators_know_their_sizes() {
+        let s: Set<u8, 4> = [1, 2, 3].into_iter().collect();
+        let t: Set<u8, 4> = [3, 4].into_iter().collect();
+        let mut i = s.iter();
+        assert_eq!(3, i.len());
+        assert_eq!(Some(&3), i.next_back());
+        assert_eq!(2, i.len());
+        assert_eq!(3, s.clone().into_iter().len());
+        assert_eq!((3, Some(5)), s.union(&t).size_hint());
+        assert_eq!((0, Some(3)), s.intersection(&t).size_hint());
+        assert_eq!((0, Some(3)), s.difference(&t).size_hint());
+        assert_eq!((0, Some(5)), s.symmetric_difference(&t).size_hint());
+    }
+
+    #[test]
+    fn clones_and_prints_iterators() {
+        let s: Set<u8, 4> = [1, 2, 3].into_iter().collect();
+        let t: Set<u8, 4> = [3, 4].into_iter().collect();
+        let u = s.union(&t);
+        assert_eq!(4, u.clone().count());
+        assert_eq!("[1, 2, 3, 4]", format!("{u:?}"));
+        assert_eq!("[3]", format!("{:?}", s.intersection(&t)));
+        assert_eq!("[1, 2]", format!("{:?}", s.difference(&t)));
+        assert_eq!("[1, 2, 4]", format!("{:?}", s.symmetric_difference(&t)));
+        assert_eq!("[1, 2, 3]", format!("{:?}", s.iter()));
+        assert_eq!("[1, 2, 3]", format!("{:?}", s.into_iter()));
+    }
+}
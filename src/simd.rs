@@ -0,0 +1,285 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use core::any::TypeId;
+use core::mem::{offset_of, size_of, MaybeUninit};
+use core::ptr;
+use core::slice;
+
+/// How many keys the portable scan compares at once.
+const CHUNK: usize = 16;
+
+/// The unsigned integer of the same width as `usize`.
+#[cfg(target_pointer_width = "16")]
+type Word = u16;
+#[cfg(target_pointer_width = "32")]
+type Word = u32;
+#[cfg(target_pointer_width = "64")]
+type Word = u64;
+
+/// Find the position of the key in a packed array of keys, comparing many
+/// keys at once.
+///
+/// It returns `None` if the keys are not primitive unsigned integers, or the
+/// key searched for is of some other type, and the caller has to compare
+/// the keys one by one.
+#[inline]
+#[allow(clippy::option_option)]
+pub fn find_key<K, Q: ?Sized>(keys: &[K], k: &Q) -> Option<Option<usize>> {
+    let t = primitive::<K, Q>()?;
+    macro_rules! packed {
+        ($t:ty) => {
+            if t == TypeId::of::<$t>() {
+                let keys = unsafe { slice::from_raw_parts(keys.as_ptr().cast::<$t>(), keys.len()) };
+                let k = unsafe { ptr::from_ref(k).cast::<$t>().read() };
+                return Some(<$t as Lane>::find(keys, k));
+            }
+        };
+    }
+    packed!(u8);
+    packed!(u16);
+    packed!(u32);
+    packed!(u64);
+    None
+}
+
+/// Find the position of the key in an array of pairs, comparing many
+/// keys at once, if the values take no space and the keys are packed
+/// just like in [`find_key`].
+///
+/// When the keys are interleaved with the values, comparing them in chunks
+/// is not faster than one by one, so `None` is returned and the caller
+/// has to compare the keys one by one.
+#[inline]
+#[allow(clippy::option_option)]
+pub fn find_pair<K, V, Q: ?Sized>(pairs: &[MaybeUninit<(K, V)>], k: &Q) -> Option<Option<usize>> {
+    if size_of::<(K, V)>() != size_of::<K>() || offset_of!((K, V), 0) != 0 {
+        return None;
+    }
+    let keys = unsafe { slice::from_raw_parts(pairs.as_ptr().cast::<K>(), pairs.len()) };
+    find_key(keys, k)
+}
+
+/// The type of the keys, if they are primitive unsigned integers and the key
+/// searched for is of the same type.
+#[inline]
+fn primitive<K, Q: ?Sized>() -> Option<TypeId> {
+    let t = typeid::of::<K>();
+    if t != typeid::of::<Q>() {
+        return None;
+    }
+    [
+        TypeId::of::<u8>(),
+        TypeId::of::<u16>(),
+        TypeId::of::<u32>(),
+        TypeId::of::<u64>(),
+        TypeId::of::<usize>(),
+    ]
+    .into_iter()
+    .position(|p| p == t)
+    .map(|i| if i == 4 { TypeId::of::<Word>() } else { t })
+}
+
+/// Compare the key with all keys, in chunks of [`CHUNK`], building a bit
+/// mask of matches in each chunk without branching, which the compiler
+/// turns into vector instructions, where the CPU has them.
+#[inline]
+fn chunked<T: Copy + PartialEq>(keys: &[T], k: T) -> Option<usize> {
+    let chunks = keys.chunks_exact(CHUNK);
+    let rest = chunks.remainder();
+    for (c, chunk) in chunks.enumerate() {
+        let mask = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |m, (j, x)| m | (u32::from(*x == k) << j));
+        if mask != 0 {
+            return Some(c * CHUNK + mask.trailing_zeros() as usize);
+        }
+    }
+    let done = keys.len() - rest.len();
+    rest.iter().position(|x| *x == k).map(|j| done + j)
+}
+
+/// An unsigned integer, which can be compared with many others at once.
+trait Lane: Copy + PartialEq + Sized {
+    /// Find the position of the key in the array.
+    #[inline]
+    fn find(keys: &[Self], k: Self) -> Option<usize> {
+        chunked(keys, k)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+impl Lane for u8 {}
+#[cfg(not(target_arch = "x86_64"))]
+impl Lane for u16 {}
+#[cfg(not(target_arch = "x86_64"))]
+impl Lane for u32 {}
+#[cfg(not(target_arch = "x86_64"))]
+impl Lane for u64 {}
+
+/// On x86-64, SSE2 is always available, so the keys are compared explicitly,
+/// sixteen bytes at a time.
+#[cfg(target_arch = "x86_64")]
+mod sse2 {
+    use super::{chunked, Lane};
+    use core::arch::x86_64::{
+        __m128i, _mm_and_si128, _mm_cmpeq_epi16, _mm_cmpeq_epi32, _mm_cmpeq_epi8, _mm_loadu_si128,
+        _mm_movemask_epi8, _mm_set1_epi16, _mm_set1_epi32, _mm_set1_epi64x, _mm_set1_epi8,
+        _mm_shuffle_epi32,
+    };
+    use core::mem::size_of;
+
+    /// Find the key in the array, sixteen bytes at a time, where `eq` fills
+    /// each lane with ones if it is equal to the key.
+    ///
+    /// The intrinsics are called in `unsafe` blocks only because they are
+    /// marked with `#[target_feature(enable = "sse2")]`, while SSE2 is a part
+    /// of the x86-64 baseline.
+    #[inline]
+    fn find<T: Copy + PartialEq>(
+        keys: &[T],
+        k: T,
+        needle: __m128i,
+        eq: impl Fn(__m128i, __m128i) -> __m128i,
+    ) -> Option<usize> {
+        let lanes = 16 / size_of::<T>();
+        let chunks = keys.chunks_exact(lanes);
+        let rest = chunks.remainder();
+        for (c, chunk) in chunks.enumerate() {
+            let mask =
+                unsafe { _mm_movemask_epi8(eq(_mm_loadu_si128(chunk.as_ptr().cast()), needle)) };
+            if mask != 0 {
+                return Some(c * lanes + mask.trailing_zeros() as usize / size_of::<T>());
+            }
+        }
+        let done = keys.len() - rest.len();
+        chunked(rest, k).map(|j| done + j)
+    }
+
+    impl Lane for u8 {
+        #[inline]
+        fn find(keys: &[Self], k: Self) -> Option<usize> {
+            unsafe {
+                find(keys, k, _mm_set1_epi8(k.cast_signed()), |a, b| {
+                    _mm_cmpeq_epi8(a, b)
+                })
+            }
+        }
+    }
+
+    impl Lane for u16 {
+        #[inline]
+        fn find(keys: &[Self], k: Self) -> Option<usize> {
+            unsafe {
+                find(keys, k, _mm_set1_epi16(k.cast_signed()), |a, b| {
+                    _mm_cmpeq_epi16(a, b)
+                })
+            }
+        }
+    }
+
+    impl Lane for u32 {
+        #[inline]
+        fn find(keys: &[Self], k: Self) -> Option<usize> {
+            unsafe {
+                find(keys, k, _mm_set1_epi32(k.cast_signed()), |a, b| {
+                    _mm_cmpeq_epi32(a, b)
+                })
+            }
+        }
+    }
+
+    impl Lane for u64 {
+        #[inline]
+        fn find(keys: &[Self], k: Self) -> Option<usize> {
+            unsafe {
+                find(keys, k, _mm_set1_epi64x(k.cast_signed()), |a, b| {
+                    let halves = _mm_cmpeq_epi32(a, b);
+                    _mm_and_si128(halves, _mm_shuffle_epi32(halves, 0b10_11_00_01))
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn finds_in_packed_keys() {
+        let keys: Vec<u32> = (0..100).collect();
+        for (i, k) in keys.iter().enumerate() {
+            assert_eq!(Some(Some(i)), find_key(&keys, k));
+        }
+        assert_eq!(Some(None), find_key(&keys, &100u32));
+        assert_eq!(Some(None), find_key(&keys[..0], &0u32));
+    }
+
+    #[test]
+    fn finds_all_widths() {
+        let bytes: Vec<u8> = (0..=255).rev().collect();
+        assert_eq!(Some(Some(255)), find_key(&bytes, &0u8));
+        let shorts: Vec<u16> = (0..40).map(|i| i * 1000).collect();
+        assert_eq!(Some(Some(33)), find_key(&shorts, &33000u16));
+        let longs: Vec<u64> = (0..40).map(|i| i << 32).collect();
+        assert_eq!(Some(Some(7)), find_key(&longs, &(7u64 << 32)));
+        assert_eq!(Some(None), find_key(&longs, &7u64));
+        let words: Vec<usize> = (0..40).collect();
+        assert_eq!(Some(Some(39)), find_key(&words, &39usize));
+    }
+
+    #[test]
+    fn compares_in_portable_chunks() {
+        let keys: Vec<u16> = (0..40).collect();
+        for (i, k) in keys.iter().enumerate() {
+            assert_eq!(Some(i), chunked(&keys, *k));
+        }
+        assert_eq!(None, chunked(&keys, 40));
+        assert_eq!(Some(2), chunked(&[1u8, 2, 3, 3], 3));
+    }
+
+    #[test]
+    fn finds_first_of_duplicates() {
+        let keys = [1u64, 2, 3, 2, 2];
+        assert_eq!(Some(Some(1)), find_key(&keys, &2u64));
+    }
+
+    #[test]
+    fn finds_in_pairs() {
+        let pairs: Vec<MaybeUninit<(u32, [u8; 3])>> =
+            (0..50).map(|i| MaybeUninit::new((i, [0; 3]))).collect();
+        assert_eq!(None, find_pair(&pairs, &42u32));
+        let keys: Vec<MaybeUninit<(u16, ())>> =
+            (0..50).map(|i| MaybeUninit::new((i, ()))).collect();
+        assert_eq!(Some(Some(17)), find_pair(&keys, &17u16));
+    }
+
+    #[test]
+    fn skips_other_types() {
+        assert_eq!(None, find_key(&[1i32, 2], &2i32));
+        assert_eq!(None, find_key(&["a", "b"], "b"));
+        assert_eq!(None, find_key(&[1u32, 2], &2u64));
+        let pairs = [MaybeUninit::new((1i64, 1))];
+        assert_eq!(None, find_pair(&pairs, &1i64));
+    }
+}
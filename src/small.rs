@@ -0,0 +1,745 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{
+    IntoIter, Map, SmallIntoIter, SmallIter, SmallIterMut, SmallKeys, SmallMap, SmallValues,
+};
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::{Index, IndexMut};
+
+/// The storage of the pairs of the [`SmallMap`].
+pub enum Repr<K: PartialEq, V, const N: usize> {
+    /// The pairs are inside the map.
+    Inline(Map<K, V, N>),
+    /// The pairs are on the heap.
+    Heap(Vec<(K, V)>),
+}
+
+/// The consuming iterator over the storage of the [`SmallMap`].
+pub enum IntoIterRepr<K: PartialEq, V, const N: usize> {
+    /// The pairs were inside the map.
+    Inline(IntoIter<K, V, N>),
+    /// The pairs were on the heap.
+    Heap(alloc::vec::IntoIter<(K, V)>),
+}
+
+impl<K: PartialEq, V, const N: usize> SmallMap<K, V, N> {
+    /// Make it, with all pairs inside.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            repr: Repr::Inline(Map::new()),
+        }
+    }
+
+    /// Are the pairs kept inside the map, not on the heap?
+    #[inline]
+    #[must_use]
+    pub const fn is_inline(&self) -> bool {
+        matches!(self.repr, Repr::Inline(_))
+    }
+
+    /// Get the number of pairs it can hold without allocating more memory.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        match &self.repr {
+            Repr::Inline(_) => N,
+            Repr::Heap(v) => v.capacity(),
+        }
+    }
+
+    /// Is it empty?
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the total number of pairs inside.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        match &self.repr {
+            Repr::Inline(m) => m.len(),
+            Repr::Heap(v) => v.len(),
+        }
+    }
+
+    /// Does the map contain this key?
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q: PartialEq + ?Sized>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).is_some()
+    }
+
+    /// Get a reference to a single value.
+    #[inline]
+    #[must_use]
+    pub fn get<Q: PartialEq + ?Sized>(&self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| &self.as_slice()[i].1)
+    }
+
+    /// Get a mutable reference to a single value.
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| &mut self.as_mut_slice()[i].1)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[inline]
+    pub fn get_key_value<Q: PartialEq + ?Sized>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| {
+            let p = &self.as_slice()[i];
+            (&p.0, &p.1)
+        })
+    }
+
+    /// Insert a single pair into the map.
+    ///
+    /// If the map did have this key present, the value is updated, and
+    /// the old value is returned. The key is not updated. If there is no
+    /// more space inside the map, all pairs are moved to the heap.
+    #[inline]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        if let Some(i) = self.position(&k) {
+            return Some(mem::replace(&mut self.as_mut_slice()[i].1, v));
+        }
+        match &mut self.repr {
+            Repr::Inline(m) if m.len() < N => m.push(k, v),
+            Repr::Inline(m) => {
+                let mut pairs = Vec::with_capacity(N * 2 + 1);
+                pairs.extend(mem::take(m));
+                pairs.push((k, v));
+                self.repr = Repr::Heap(pairs);
+            }
+            Repr::Heap(pairs) => pairs.push((k, v)),
+        }
+        None
+    }
+
+    /// Remove by key, returning the value if the key was previously in the map.
+    ///
+    /// The last pair in the map takes the place of the removed one.
+    #[inline]
+    pub fn remove<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        self.remove_entry(k).map(|p| p.1)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the
+    /// key was previously in the map.
+    #[inline]
+    pub fn remove_entry<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| match &mut self.repr {
+            Repr::Inline(m) => m.swap_take(i),
+            Repr::Heap(pairs) => pairs.swap_remove(i),
+        })
+    }
+
+    /// Remove all pairs from it and release the memory on the heap, if any,
+    /// keeping it inside again.
+    #[inline]
+    pub fn clear(&mut self) {
+        match &mut self.repr {
+            Repr::Inline(m) => m.clear(),
+            Repr::Heap(_) => self.repr = Repr::Inline(Map::new()),
+        }
+    }
+
+    /// Retains only the elements specified by the predicate, which may
+    /// also modify the values.
+    ///
+    /// The order of the retained pairs doesn't change. The number of removed
+    /// pairs is returned.
+    #[inline]
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) -> usize {
+        match &mut self.repr {
+            Repr::Inline(m) => m.retain(f),
+            Repr::Heap(pairs) => {
+                let busy = pairs.len();
+                pairs.retain_mut(|p| f(&p.0, &mut p.1));
+                busy - pairs.len()
+            }
+        }
+    }
+
+    /// Move the pairs from the heap back inside the map, if there are
+    /// no more than `N` of them, releasing the memory on the heap.
+    ///
+    /// Returns whether the pairs are kept inside the map now.
+    #[inline]
+    pub fn shrink_to_inline(&mut self) -> bool {
+        if let Repr::Heap(pairs) = &mut self.repr {
+            if pairs.len() > N {
+                return false;
+            }
+            let mut m = Map::new();
+            for (k, v) in pairs.drain(..) {
+                m.push(k, v);
+            }
+            self.repr = Repr::Inline(m);
+        }
+        true
+    }
+
+    /// Make an iterator over all pairs.
+    #[inline]
+    pub fn iter(&self) -> SmallIter<'_, K, V> {
+        SmallIter {
+            iter: self.as_slice().iter(),
+        }
+    }
+
+    /// An iterator with mutable references to the values but
+    /// immutable references to the keys.
+    #[inline]
+    pub fn iter_mut(&mut self) -> SmallIterMut<'_, K, V> {
+        SmallIterMut {
+            iter: self.as_mut_slice().iter_mut(),
+        }
+    }
+
+    /// An iterator visiting all keys in the order of their positions.
+    #[inline]
+    pub fn keys(&self) -> SmallKeys<'_, K, V> {
+        SmallKeys { iter: self.iter() }
+    }
+
+    /// An iterator visiting all values in the order of their positions.
+    #[inline]
+    pub fn values(&self) -> SmallValues<'_, K, V> {
+        SmallValues { iter: self.iter() }
+    }
+
+    /// Internal function to find the position of the key.
+    #[inline]
+    fn position<Q: PartialEq + ?Sized>(&self, k: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        match &self.repr {
+            Repr::Inline(m) => m.position(k),
+            Repr::Heap(pairs) => pairs.iter().position(|p| p.0.borrow() == k),
+        }
+    }
+
+    /// Internal function to see all pairs as a slice.
+    #[inline]
+    fn as_slice(&self) -> &[(K, V)] {
+        match &self.repr {
+            Repr::Inline(m) => m.as_slice(),
+            Repr::Heap(pairs) => pairs,
+        }
+    }
+
+    /// Internal function to see all pairs as a mutable slice.
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [(K, V)] {
+        match &mut self.repr {
+            Repr::Inline(m) => m.as_mut_slice(),
+            Repr::Heap(pairs) => pairs,
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Default for SmallMap<K, V, N> {
+    /// Make a default empty [`SmallMap`].
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq + Clone, V: Clone, const N: usize> Clone for SmallMap<K, V, N> {
+    fn clone(&self) -> Self {
+        Self {
+            repr: match &self.repr {
+                Repr::Inline(m) => Repr::Inline(m.clone()),
+                Repr::Heap(pairs) => Repr::Heap(pairs.clone()),
+            },
+        }
+    }
+}
+
+impl<K: PartialEq, V: PartialEq, const N: usize> PartialEq for SmallMap<K, V, N> {
+    /// Two maps are equal if they have the same pairs, no matter
+    /// where they are kept.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K: Eq, V: Eq, const N: usize> Eq for SmallMap<K, V, N> {}
+
+impl<K: PartialEq + Debug, V: Debug, const N: usize> Debug for SmallMap<K, V, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> FromIterator<(K, V)> for SmallMap<K, V, N> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut m: Self = Self::new();
+        m.extend(iter);
+        m
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Extend<(K, V)> for SmallMap<K, V, N> {
+    #[inline]
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<K: PartialEq + Borrow<Q>, Q: PartialEq + ?Sized, V, const N: usize> Index<&Q>
+    for SmallMap<K, V, N>
+{
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("No entry found for the key")
+    }
+}
+
+impl<K: PartialEq + Borrow<Q>, Q: PartialEq + ?Sized, V, const N: usize> IndexMut<&Q>
+    for SmallMap<K, V, N>
+{
+    #[inline]
+    fn index_mut(&mut self, key: &Q) -> &mut V {
+        self.get_mut(key).expect("No entry found for the key")
+    }
+}
+
+impl<'a, K, V> Iterator for SmallIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|p| (&p.0, &p.1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for SmallIter<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|p| (&p.0, &p.1))
+    }
+}
+
+impl<K, V> ExactSizeIterator for SmallIter<'_, K, V> {}
+
+impl<K, V> FusedIterator for SmallIter<'_, K, V> {}
+
+impl<K, V> Clone for SmallIter<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for SmallIter<'_, K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for SmallIterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|p| (&p.0, &mut p.1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for SmallIterMut<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|p| (&p.0, &mut p.1))
+    }
+}
+
+impl<K, V> ExactSizeIterator for SmallIterMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for SmallIterMut<'_, K, V> {}
+
+impl<K: Debug, V: Debug> Debug for SmallIterMut<'_, K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter.as_slice().iter().map(|p| (&p.0, &p.1)))
+            .finish()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> SmallIntoIter<K, V, N> {
+    /// The pairs not yet returned.
+    fn as_slice(&self) -> &[(K, V)] {
+        match &self.iter {
+            IntoIterRepr::Inline(i) => i.as_slice(),
+            IntoIterRepr::Heap(i) => i.as_slice(),
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Iterator for SmallIntoIter<K, V, N> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.iter {
+            IntoIterRepr::Inline(i) => i.next(),
+            IntoIterRepr::Heap(i) => i.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.iter {
+            IntoIterRepr::Inline(i) => i.size_hint(),
+            IntoIterRepr::Heap(i) => i.size_hint(),
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> DoubleEndedIterator for SmallIntoIter<K, V, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.iter {
+            IntoIterRepr::Inline(i) => i.next_back(),
+            IntoIterRepr::Heap(i) => i.next_back(),
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> ExactSizeIterator for SmallIntoIter<K, V, N> {}
+
+impl<K: PartialEq, V, const N: usize> FusedIterator for SmallIntoIter<K, V, N> {}
+
+impl<K: PartialEq + Debug, V: Debug, const N: usize> Debug for SmallIntoIter<K, V, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for SmallKeys<'a, K, V> {
+    type Item = &'a K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|p| p.0)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for SmallKeys<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|p| p.0)
+    }
+}
+
+impl<K, V> ExactSizeIterator for SmallKeys<'_, K, V> {}
+
+impl<K, V> FusedIterator for SmallKeys<'_, K, V> {}
+
+impl<K, V> Clone for SmallKeys<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<K: Debug, V> Debug for SmallKeys<'_, K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for SmallValues<'a, K, V> {
+    type Item = &'a V;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|p| p.1)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for SmallValues<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|p| p.1)
+    }
+}
+
+impl<K, V> ExactSizeIterator for SmallValues<'_, K, V> {}
+
+impl<K, V> FusedIterator for SmallValues<'_, K, V> {}
+
+impl<K, V> Clone for SmallValues<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<K, V: Debug> Debug for SmallValues<'_, K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K: PartialEq, V, const N: usize> IntoIterator for &'a SmallMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = SmallIter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: PartialEq, V, const N: usize> IntoIterator for &'a mut SmallMap<K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = SmallIterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> IntoIterator for SmallMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = SmallIntoIter<K, V, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        SmallIntoIter {
+            iter: match self.repr {
+                Repr::Inline(m) => IntoIterRepr::Inline(m.into_iter()),
+                Repr::Heap(pairs) => IntoIterRepr::Heap(pairs.into_iter()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn spills_to_heap() {
+        let mut m: SmallMap<u32, u32, 4> = SmallMap::new();
+        for i in 0..4 {
+            assert_eq!(None, m.insert(i, i));
+        }
+        assert!(m.is_inline());
+        assert_eq!(4, m.capacity());
+        assert_eq!(None, m.insert(4, 4));
+        assert!(!m.is_inline());
+        assert!(m.capacity() >= 5);
+        assert_eq!(Some(4), m.insert(4, 40));
+        assert_eq!(5, m.len());
+        for i in 0..4 {
+            assert_eq!(Some(&i), m.get(&i));
+        }
+        assert_eq!(Some(&40), m.get(&4));
+        assert_eq!(vec![0, 1, 2, 3, 4], m.keys().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn keeps_same_api_on_heap() {
+        let mut m: SmallMap<String, i32, 1> = SmallMap::new();
+        m.insert("one".to_string(), 1);
+        m.insert("two".to_string(), 2);
+        m.insert("three".to_string(), 3);
+        assert!(m.contains_key("two"));
+        m["two"] += 20;
+        *m.get_mut("three").unwrap() += 30;
+        assert_eq!(Some((&"two".to_string(), &22)), m.get_key_value("two"));
+        assert_eq!(Some(1), m.remove("one"));
+        assert_eq!(None, m.remove("one"));
+        for (_, v) in &mut m {
+            *v += 1;
+        }
+        assert_eq!(vec![&34, &23], m.values().collect::<Vec<_>>());
+        m.retain(|_, v| *v > 30);
+        assert_eq!(
+            vec![("three".to_string(), 34)],
+            m.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn shrinks_back_inline() {
+        let mut m: SmallMap<u8, u8, 2> = (0..5).map(|i| (i, i)).collect();
+        assert!(!m.shrink_to_inline());
+        assert_eq!(3, m.retain(|k, _| *k > 2));
+        assert!(m.shrink_to_inline());
+        assert!(m.is_inline());
+        assert_eq!(vec![(&3, &3), (&4, &4)], m.iter().collect::<Vec<_>>());
+        assert!(m.shrink_to_inline());
+    }
+
+    #[test]
+    fn clears_back_inline() {
+        let mut m: SmallMap<u8, u8, 2> = (0..5).map(|i| (i, i)).collect();
+        m.clear();
+        assert!(m.is_inline());
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn compares_inline_and_heap_maps() {
+        let a: SmallMap<u8, u8, 2> = [(1, 1), (2, 2)].into_iter().collect();
+        let mut b: SmallMap<u8, u8, 2> = [(3, 3), (2, 2), (1, 1)].into_iter().collect();
+        assert_ne!(a, b);
+        b.remove(&3);
+        assert_eq!(a, b);
+        assert!(a.is_inline() && !b.is_inline());
+        assert_eq!(a, b.clone());
+    }
+
+    #[test]
+    fn debugs_map() {
+        let m: SmallMap<&str, i32, 1> = [("one", 1), ("two", 2)].into_iter().collect();
+        assert_eq!("{\"one\": 1, \"two\": 2}", format!("{m:?}"));
+    }
+
+    #[test]
+    fn drops_pairs_on_heap() {
+        use std::rc::Rc;
+        let v = Rc::new(());
+        let mut m: SmallMap<u8, Rc<()>, 1> = SmallMap::new();
+        m.insert(1, Rc::clone(&v));
+        m.insert(2, Rc::clone(&v));
+        m.insert(3, Rc::clone(&v));
+        m.remove(&2);
+        assert_eq!(3, Rc::strong_count(&v));
+        let mut i = m.into_iter();
+        drop(i.next());
+        drop(i);
+        assert_eq!(1, Rc::strong_count(&v));
+    }
+
+    #[test]
+    fn iterates_like_map() {
+        for n in [2, 3] {
+            let mut m: SmallMap<u8, u8, 2> = (0..n).map(|i| (i, i * 10)).collect();
+            assert_eq!(n as usize, m.iter().len());
+            assert_eq!(Some((&(n - 1), &((n - 1) * 10))), m.iter().next_back());
+            assert_eq!(Some(&(n - 1)), m.keys().next_back());
+            assert_eq!(Some(&0), m.values().rev().nth(n as usize - 1));
+            for (_, v) in m.iter_mut().rev().take(1) {
+                *v += 1;
+            }
+            assert_eq!(Some(&((n - 1) * 10 + 1)), m.values().next_back());
+            let mut i = m.into_iter();
+            assert_eq!((n as usize, Some(n as usize)), i.size_hint());
+            assert_eq!(Some((0, 0)), i.next());
+            assert_eq!(n as usize - 1, i.len());
+            assert_eq!(Some((n - 1, (n - 1) * 10 + 1)), i.next_back());
+        }
+    }
+
+    #[test]
+    fn debugs_iterators() {
+        for n in [2, 3] {
+            let mut m: SmallMap<u8, u8, 2> = (0..n).map(|i| (i, i)).collect();
+            let mut i = m.iter();
+            i.next();
+            assert_eq!(
+                format!("{:?}", (1..n).map(|k| (k, k)).collect::<Vec<_>>()),
+                format!("{i:?}")
+            );
+            assert_eq!(
+                format!("{:?}", (0..n).collect::<Vec<_>>()),
+                format!("{:?}", m.keys())
+            );
+            assert_eq!(
+                format!("{:?}", (0..n).collect::<Vec<_>>()),
+                format!("{:?}", m.values())
+            );
+            assert_eq!(format!("{:?}", m.iter()), format!("{:?}", m.iter_mut()));
+            let mut i = m.clone().into_iter();
+            i.next_back();
+            assert_eq!(
+                format!("{:?}", (0..n - 1).map(|k| (k, k)).collect::<Vec<_>>()),
+                format!("{i:?}")
+            );
+        }
+    }
+}
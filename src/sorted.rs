@@ -0,0 +1,350 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::error::OVERFLOW;
+use crate::{CapacityError, Map, SortedMap};
+use core::borrow::Borrow;
+use core::mem;
+use core::ops::{Index, IndexMut};
+
+impl<K: Ord, V, const N: usize> SortedMap<K, V, N> {
+    /// Get its total capacity.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Is it empty?
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Return the total number of pairs inside.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Does the map contain this key?
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q: Ord + ?Sized>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.search(k).is_ok()
+    }
+
+    /// Get a reference to a single value.
+    #[inline]
+    #[must_use]
+    pub fn get<Q: Ord + ?Sized>(&self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.search(k).ok().map(|i| &self.map.item(i).1)
+    }
+
+    /// Get a mutable reference to a single value.
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q: Ord + ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        self.search(k).ok().map(|i| &mut self.map.item_mut(i).1)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[inline]
+    pub fn get_key_value<Q: Ord + ?Sized>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.search(k).ok().map(|i| {
+            let p = self.map.item(i);
+            (&p.0, &p.1)
+        })
+    }
+
+    /// Insert a single pair into the map, right where its key belongs.
+    ///
+    /// If the map did have this key present, the value is updated, and
+    /// the old value is returned. The key is not updated.
+    ///
+    /// # Panics
+    ///
+    /// If there are too many pairs in the map already.
+    #[inline]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        self.try_insert(k, v)
+            .unwrap_or_else(|_| panic!("{OVERFLOW}"))
+    }
+
+    /// Try to insert a single pair into the map, right where its key belongs.
+    ///
+    /// # Errors
+    ///
+    /// If the key is absent and there is no more space in the map,
+    /// a [`CapacityError`] is returned, with the rejected pair inside.
+    #[inline]
+    pub fn try_insert(&mut self, k: K, v: V) -> Result<Option<V>, CapacityError<K, V>> {
+        match self.search(&k) {
+            Ok(i) => Ok(Some(mem::replace(&mut self.map.item_mut(i).1, v))),
+            Err(i) if self.len() < N => {
+                self.map.push(k, v);
+                self.map.pairs[i..self.map.len].rotate_right(1);
+                Ok(None)
+            }
+            Err(_) => Err(CapacityError::new(k, v)),
+        }
+    }
+
+    /// Remove by key, returning the value if the key was previously in the map.
+    ///
+    /// All pairs after the removed one are shifted, to keep them sorted.
+    #[inline]
+    pub fn remove<Q: Ord + ?Sized>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        self.remove_entry(k).map(|p| p.1)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the
+    /// key was previously in the map.
+    #[inline]
+    pub fn remove_entry<Q: Ord + ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.search(k).ok().map(|i| self.map.shift_take(i))
+    }
+
+    /// Remove all pairs from it, but keep the space intact for future use.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Retains only the elements specified by the predicate, which may
+    /// also modify the values, returning the number of removed pairs.
+    #[inline]
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, f: F) -> usize {
+        self.map.retain(f)
+    }
+
+    /// Returns the pair with the smallest key.
+    #[inline]
+    #[must_use]
+    pub const fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.map.first()
+    }
+
+    /// Returns the pair with the largest key.
+    #[inline]
+    #[must_use]
+    pub const fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.map.last()
+    }
+
+    /// Removes and returns the pair with the smallest key.
+    #[inline]
+    pub const fn pop_first(&mut self) -> Option<(K, V)> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.map.shift_take(0))
+        }
+    }
+
+    /// Removes and returns the pair with the largest key.
+    #[inline]
+    pub const fn pop_last(&mut self) -> Option<(K, V)> {
+        self.map.pop()
+    }
+
+    /// Internal function to find the key with a binary search, returning
+    /// either its position or the position where it should be inserted.
+    #[inline]
+    fn search<Q: Ord + ?Sized>(&self, k: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+    {
+        self.map
+            .as_slice()
+            .binary_search_by(|p| p.0.borrow().cmp(k))
+    }
+}
+
+impl<K: Ord, V, const N: usize> From<Map<K, V, N>> for SortedMap<K, V, N> {
+    /// Make a [`SortedMap`] from a [`Map`], sorting its pairs.
+    #[inline]
+    fn from(mut map: Map<K, V, N>) -> Self {
+        map.as_mut_slice().sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        Self { map }
+    }
+}
+
+impl<K: Ord, V, const N: usize> FromIterator<(K, V)> for SortedMap<K, V, N> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from(Map::from_iter(iter))
+    }
+}
+
+impl<K: Ord + Clone, V: Clone, const N: usize> Clone for SortedMap<K, V, N> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<K: Ord, V: PartialEq, const N: usize> PartialEq for SortedMap<K, V, N> {
+    /// Two maps are equal if they have the same pairs, which are
+    /// compared one by one, since they are sorted the same way.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.map.as_slice() == other.map.as_slice()
+    }
+}
+
+impl<K: Ord, V: Eq, const N: usize> Eq for SortedMap<K, V, N> {}
+
+impl<K: Ord + Borrow<Q>, Q: Ord + ?Sized, V, const N: usize> Index<&Q> for SortedMap<K, V, N> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("No entry found for the key")
+    }
+}
+
+impl<K: Ord + Borrow<Q>, Q: Ord + ?Sized, V, const N: usize> IndexMut<&Q> for SortedMap<K, V, N> {
+    #[inline]
+    fn index_mut(&mut self, key: &Q) -> &mut V {
+        self.get_mut(key).expect("No entry found for the key")
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn inserts_in_order() {
+        let mut m: SortedMap<i32, &str, 8> = SortedMap::new();
+        assert_eq!(None, m.insert(3, "c"));
+        assert_eq!(None, m.insert(1, "a"));
+        assert_eq!(None, m.insert(2, "b"));
+        assert_eq!(Some("c"), m.insert(3, "C"));
+        assert_eq!(vec![&1, &2, &3], m.keys().collect::<Vec<_>>());
+        assert_eq!(vec![&"a", &"b", &"C"], m.values().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn gets_by_borrowed_key() {
+        let mut m: SortedMap<String, i32, 8> = SortedMap::new();
+        m.insert("b".to_string(), 2);
+        m.insert("a".to_string(), 1);
+        assert_eq!(Some(&1), m.get("a"));
+        assert_eq!(Some((&"b".to_string(), &2)), m.get_key_value("b"));
+        assert!(m.contains_key("b"));
+        assert!(!m.contains_key("c"));
+        m["a"] += 10;
+        assert_eq!(11, m["a"]);
+        *m.get_mut("b").unwrap() = 5;
+        assert_eq!(5, m["b"]);
+    }
+
+    #[test]
+    fn removes_and_keeps_order() {
+        let mut m: SortedMap<i32, i32, 8> = (0..6).map(|k| (k, k)).collect();
+        assert_eq!(Some(2), m.remove(&2));
+        assert_eq!(None, m.remove(&2));
+        assert_eq!(Some((0, 0)), m.remove_entry(&0));
+        assert_eq!(vec![&1, &3, &4, &5], m.keys().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn pops_from_both_ends() {
+        let mut m: SortedMap<i32, i32, 8> = [(5, 50), (1, 10), (3, 30)].into_iter().collect();
+        assert_eq!(Some((&1, &10)), m.first_key_value());
+        assert_eq!(Some((&5, &50)), m.last_key_value());
+        assert_eq!(Some((1, 10)), m.pop_first());
+        assert_eq!(Some((5, 50)), m.pop_last());
+        assert_eq!(Some((3, 30)), m.pop_last());
+        assert_eq!(None, m.pop_first());
+        assert_eq!(None, m.pop_last());
+        assert_eq!(None, m.first_key_value());
+    }
+
+    #[test]
+    fn rejects_overflow() {
+        let mut m: SortedMap<i32, i32, 2> = SortedMap::new();
+        m.insert(2, 2);
+        m.insert(1, 1);
+        assert_eq!((0, 0), m.try_insert(0, 0).unwrap_err().into_pair());
+        assert_eq!(Some(2), m.try_insert(2, 3).unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "No more keys available in the map")]
+    fn panics_on_overflow() {
+        let mut m: SortedMap<i32, i32, 1> = SortedMap::new();
+        m.insert(1, 1);
+        m.insert(0, 0);
+    }
+
+    #[test]
+    fn sorts_map() {
+        let mut m: Map<i32, i32, 4> = Map::new();
+        m.insert(4, 4);
+        m.insert(2, 2);
+        m.insert(3, 3);
+        let s = SortedMap::from(m);
+        assert_eq!(vec![&2, &3, &4], s.keys().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn compares_maps() {
+        let a: SortedMap<i32, i32, 4> = [(1, 1), (2, 2)].into_iter().collect();
+        let b: SortedMap<i32, i32, 4> = [(2, 2), (1, 1)].into_iter().collect();
+        let mut c = a.clone();
+        assert_eq!(a, b);
+        c.insert(3, 3);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn retains_in_order() {
+        let mut m: SortedMap<i32, i32, 8> = (0..8).rev().map(|k| (k, k)).collect();
+        m.retain(|k, _| k % 3 == 0);
+        assert_eq!(vec![&0, &3, &6], m.keys().collect::<Vec<_>>());
+        m.clear();
+        assert!(m.is_empty());
+    }
+}
@@ -0,0 +1,705 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::error::OVERFLOW;
+use crate::{CapacityError, SplitIntoIter, SplitMap};
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+use core::iter::Zip;
+use core::mem;
+use core::mem::MaybeUninit;
+use core::ops::{Index, IndexMut};
+use core::ptr;
+use core::slice;
+
+impl<K: PartialEq, V, const N: usize> SplitMap<K, V, N> {
+    /// Make it.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::uninit_assumed_init)]
+    pub const fn new() -> Self {
+        unsafe {
+            Self {
+                len: 0,
+                keys: MaybeUninit::<[MaybeUninit<K>; N]>::uninit().assume_init(),
+                values: MaybeUninit::<[MaybeUninit<V>; N]>::uninit().assume_init(),
+            }
+        }
+    }
+
+    /// Get its total capacity.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Is it empty?
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the total number of pairs inside.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Does the map contain this key?
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q: PartialEq + ?Sized>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).is_some()
+    }
+
+    /// Get a reference to a single value.
+    #[inline]
+    #[must_use]
+    pub fn get<Q: PartialEq + ?Sized>(&self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| &self.value_slice()[i])
+    }
+
+    /// Get a mutable reference to a single value.
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| &mut self.value_slice_mut()[i])
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[inline]
+    pub fn get_key_value<Q: PartialEq + ?Sized>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k)
+            .map(|i| (&self.key_slice()[i], &self.value_slice()[i]))
+    }
+
+    /// Insert a single pair into the map.
+    ///
+    /// If the map did have this key present, the value is updated, and
+    /// the old value is returned. The key is not updated.
+    ///
+    /// # Panics
+    ///
+    /// If there are too many pairs in the map already.
+    #[inline]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        self.try_insert(k, v)
+            .unwrap_or_else(|_| panic!("{OVERFLOW}"))
+    }
+
+    /// Try to insert a single pair into the map.
+    ///
+    /// # Errors
+    ///
+    /// If the key is absent and there is no more space in the map,
+    /// a [`CapacityError`] is returned, with the rejected pair inside.
+    #[inline]
+    pub fn try_insert(&mut self, k: K, v: V) -> Result<Option<V>, CapacityError<K, V>> {
+        if let Some(i) = self.position(&k) {
+            return Ok(Some(mem::replace(&mut self.value_slice_mut()[i], v)));
+        }
+        if self.len == N {
+            return Err(CapacityError::new(k, v));
+        }
+        self.keys[self.len].write(k);
+        self.values[self.len].write(v);
+        self.len += 1;
+        Ok(None)
+    }
+
+    /// Remove by key, returning the value if the key was previously in the map.
+    ///
+    /// The last pair in the map takes the place of the removed one.
+    #[inline]
+    pub fn remove<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        self.remove_entry(k).map(|p| p.1)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the
+    /// key was previously in the map.
+    #[inline]
+    pub fn remove_entry<Q: PartialEq + ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k).map(|i| {
+            self.len -= 1;
+            self.keys.swap(i, self.len);
+            self.values.swap(i, self.len);
+            unsafe {
+                (
+                    self.keys[self.len].assume_init_read(),
+                    self.values[self.len].assume_init_read(),
+                )
+            }
+        })
+    }
+
+    /// Remove all pairs from it, but keep the space intact for future use.
+    ///
+    /// All keys and values are dropped. If one of the destructors panics,
+    /// the rest of the pairs are still dropped and the map stays empty.
+    #[inline]
+    pub fn clear(&mut self) {
+        let busy = self.len;
+        self.len = 0;
+        drop(Dropper {
+            keys: self.keys.as_mut_ptr().cast::<K>(),
+            values: self.values.as_mut_ptr().cast::<V>(),
+            left: busy,
+        });
+    }
+
+    /// Retains only the elements specified by the predicate, which may
+    /// also modify the values.
+    ///
+    /// The order of the retained pairs doesn't change. The number of removed
+    /// pairs is returned. If the predicate or a destructor panics, the pairs
+    /// not yet visited stay in the map.
+    #[inline]
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) -> usize {
+        let busy = self.len;
+        self.len = 0;
+        let mut g = Retainer {
+            map: self,
+            busy,
+            seen: 0,
+            kept: 0,
+        };
+        while g.seen < g.busy {
+            let i = g.seen;
+            let keep = unsafe {
+                f(
+                    g.map.keys[i].assume_init_ref(),
+                    g.map.values[i].assume_init_mut(),
+                )
+            };
+            g.seen += 1;
+            if keep {
+                g.map.keys.swap(g.kept, i);
+                g.map.values.swap(g.kept, i);
+                g.kept += 1;
+            } else {
+                unsafe {
+                    drop((
+                        g.map.keys[i].assume_init_read(),
+                        g.map.values[i].assume_init_read(),
+                    ));
+                }
+            }
+        }
+        busy - g.kept
+    }
+
+    /// An iterator visiting all keys in the order of their positions.
+    #[inline]
+    pub fn keys(&self) -> slice::Iter<'_, K> {
+        self.key_slice().iter()
+    }
+
+    /// An iterator visiting all values in the order of their positions.
+    #[inline]
+    pub fn values(&self) -> slice::Iter<'_, V> {
+        self.value_slice().iter()
+    }
+
+    /// An iterator visiting all values mutably in the order of their positions.
+    #[inline]
+    pub fn values_mut(&mut self) -> slice::IterMut<'_, V> {
+        self.value_slice_mut().iter_mut()
+    }
+
+    /// Make an iterator over all pairs.
+    #[inline]
+    pub fn iter(&self) -> Zip<slice::Iter<'_, K>, slice::Iter<'_, V>> {
+        self.keys().zip(self.values())
+    }
+
+    /// An iterator with mutable references to the values but
+    /// immutable references to the keys.
+    #[inline]
+    pub fn iter_mut(&mut self) -> Zip<slice::Iter<'_, K>, slice::IterMut<'_, V>> {
+        let (keys, values) = unsafe {
+            (
+                slice::from_raw_parts(self.keys.as_ptr().cast::<K>(), self.len),
+                slice::from_raw_parts_mut(self.values.as_mut_ptr().cast::<V>(), self.len),
+            )
+        };
+        keys.iter().zip(values.iter_mut())
+    }
+
+    /// Internal function to find the position of the key, looking only at keys.
+    #[inline]
+    fn position<Q: PartialEq + ?Sized>(&self, k: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        #[cfg(feature = "simd")]
+        if let Some(i) = crate::simd::find_key(self.key_slice(), k) {
+            return i;
+        }
+        self.key_slice().iter().position(|x| x.borrow() == k)
+    }
+
+    /// Internal function to see all keys as a slice.
+    #[inline]
+    const fn key_slice(&self) -> &[K] {
+        unsafe { slice::from_raw_parts(self.keys.as_ptr().cast(), self.len) }
+    }
+
+    /// Internal function to see all values as a slice.
+    #[inline]
+    const fn value_slice(&self) -> &[V] {
+        unsafe { slice::from_raw_parts(self.values.as_ptr().cast(), self.len) }
+    }
+
+    /// Internal function to see all values as a mutable slice.
+    #[inline]
+    const fn value_slice_mut(&mut self) -> &mut [V] {
+        unsafe { slice::from_raw_parts_mut(self.values.as_mut_ptr().cast(), self.len) }
+    }
+}
+
+/// Drops the pairs, one key and its value at a time, going on with the
+/// rest of them if one of the destructors panics.
+struct Dropper<K, V> {
+    keys: *mut K,
+    values: *mut V,
+    left: usize,
+}
+
+impl<K, V> Drop for Dropper<K, V> {
+    fn drop(&mut self) {
+        while self.left > 0 {
+            unsafe {
+                let pair = (self.keys.read(), self.values.read());
+                self.keys = self.keys.add(1);
+                self.values = self.values.add(1);
+                self.left -= 1;
+                let rest = Self {
+                    keys: self.keys,
+                    values: self.values,
+                    left: self.left,
+                };
+                drop(pair);
+                mem::forget(rest);
+            }
+        }
+    }
+}
+
+/// The state of [`SplitMap::retain`], which keeps the map consistent
+/// if the predicate or a destructor panics.
+struct Retainer<'a, K: PartialEq, V, const N: usize> {
+    map: &'a mut SplitMap<K, V, N>,
+    busy: usize,
+    seen: usize,
+    kept: usize,
+}
+
+impl<K: PartialEq, V, const N: usize> Drop for Retainer<'_, K, V, N> {
+    /// Close the gap left by the removed pairs, keeping the pairs
+    /// not yet visited in the map.
+    fn drop(&mut self) {
+        let rest = self.busy - self.seen;
+        if self.kept < self.seen {
+            unsafe {
+                let keys = self.map.keys.as_mut_ptr();
+                ptr::copy(keys.add(self.seen), keys.add(self.kept), rest);
+                let values = self.map.values.as_mut_ptr();
+                ptr::copy(values.add(self.seen), values.add(self.kept), rest);
+            }
+        }
+        self.map.len = self.kept + rest;
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Default for SplitMap<K, V, N> {
+    /// Make a default empty [`SplitMap`].
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Drop for SplitMap<K, V, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K: Clone + PartialEq, V: Clone, const N: usize> Clone for SplitMap<K, V, N> {
+    fn clone(&self) -> Self {
+        let mut m: Self = Self::new();
+        for (k, v) in self {
+            let (k, v) = (k.clone(), v.clone());
+            m.keys[m.len].write(k);
+            m.values[m.len].write(v);
+            m.len += 1;
+        }
+        m
+    }
+}
+
+impl<K: PartialEq, V: PartialEq, const N: usize> PartialEq for SplitMap<K, V, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K: Eq, V: Eq, const N: usize> Eq for SplitMap<K, V, N> {}
+
+impl<K: PartialEq + Debug, V: Debug, const N: usize> Debug for SplitMap<K, V, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> FromIterator<(K, V)> for SplitMap<K, V, N> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut m: Self = Self::new();
+        m.extend(iter);
+        m
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Extend<(K, V)> for SplitMap<K, V, N> {
+    /// Insert all pairs, updating the values of the keys already in the map.
+    ///
+    /// # Panics
+    ///
+    /// If there is no space for a new key.
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<'a, K: PartialEq + Copy + 'a, V: Copy + 'a, const N: usize> Extend<(&'a K, &'a V)>
+    for SplitMap<K, V, N>
+{
+    /// Insert copies of all pairs, like the [`Extend`] of owned pairs does.
+    ///
+    /// # Panics
+    ///
+    /// If there is no space for a new key.
+    #[inline]
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(k, v)| (*k, *v)));
+    }
+}
+
+impl<'a, K: PartialEq, V, const N: usize> IntoIterator for &'a SplitMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Zip<slice::Iter<'a, K>, slice::Iter<'a, V>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: PartialEq, V, const N: usize> IntoIterator for &'a mut SplitMap<K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = Zip<slice::Iter<'a, K>, slice::IterMut<'a, V>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> IntoIterator for SplitMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = SplitIntoIter<K, V, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        SplitIntoIter { pos: 0, map: self }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> SplitIntoIter<K, V, N> {
+    /// Internal function to read the pair at the position, which
+    /// is not visited again.
+    #[inline]
+    const unsafe fn read_at(&self, i: usize) -> (K, V) {
+        (
+            self.map.keys[i].assume_init_read(),
+            self.map.values[i].assume_init_read(),
+        )
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Iterator for SplitIntoIter<K, V, N> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos < self.map.len {
+            let p = unsafe { self.read_at(self.pos) };
+            self.pos += 1;
+            Some(p)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.map.len - self.pos;
+        (n, Some(n))
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> DoubleEndedIterator for SplitIntoIter<K, V, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pos < self.map.len {
+            self.map.len -= 1;
+            Some(unsafe { self.read_at(self.map.len) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> ExactSizeIterator for SplitIntoIter<K, V, N> {}
+
+impl<K: PartialEq, V, const N: usize> FusedIterator for SplitIntoIter<K, V, N> {}
+
+impl<K: PartialEq + Debug, V: Debug, const N: usize> Debug for SplitIntoIter<K, V, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rest = self.map.key_slice()[self.pos..]
+            .iter()
+            .zip(&self.map.value_slice()[self.pos..]);
+        f.debug_list().entries(rest).finish()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Drop for SplitIntoIter<K, V, N> {
+    fn drop(&mut self) {
+        let busy = self.map.len;
+        self.map.len = 0;
+        drop(Dropper {
+            keys: unsafe { self.map.keys.as_mut_ptr().cast::<K>().add(self.pos) },
+            values: unsafe { self.map.values.as_mut_ptr().cast::<V>().add(self.pos) },
+            left: busy - self.pos,
+        });
+    }
+}
+
+impl<K: Eq + Borrow<Q>, Q: Eq + ?Sized, V, const N: usize> Index<&Q> for SplitMap<K, V, N> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("No entry found for the key")
+    }
+}
+
+impl<K: Eq + Borrow<Q>, Q: Eq + ?Sized, V, const N: usize> IndexMut<&Q> for SplitMap<K, V, N> {
+    #[inline]
+    fn index_mut(&mut self, key: &Q) -> &mut V {
+        self.get_mut(key).expect("No entry found for the key")
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn insert_and_gets() {
+        let mut m: SplitMap<&str, i32, 10> = SplitMap::new();
+        assert_eq!(None, m.insert("one", 42));
+        assert_eq!(None, m.insert("two", 16));
+        assert_eq!(Some(16), m.insert("two", 17));
+        assert_eq!(2, m.len());
+        assert_eq!(Some(&42), m.get("one"));
+        assert_eq!(Some((&"two", &17)), m.get_key_value("two"));
+        assert!(m.contains_key("one"));
+        assert!(!m.contains_key("three"));
+    }
+
+    #[test]
+    fn changes_value() {
+        let mut m: SplitMap<u8, [u8; 64], 4> = SplitMap::new();
+        m.insert(1, [0; 64]);
+        m.get_mut(&1).unwrap()[7] = 42;
+        m[&1][8] = 16;
+        assert_eq!(42, m[&1][7]);
+        assert_eq!(16, m[&1][8]);
+    }
+
+    #[test]
+    fn rejects_overflow() {
+        let mut m: SplitMap<u8, u8, 1> = SplitMap::new();
+        m.insert(1, 1);
+        assert_eq!((2, 2), m.try_insert(2, 2).unwrap_err().into_pair());
+        assert_eq!(Some(1), m.try_insert(1, 3).unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "No more keys available in the map")]
+    fn panics_on_overflow() {
+        let mut m: SplitMap<u8, u8, 1> = SplitMap::new();
+        m.insert(1, 1);
+        m.insert(2, 2);
+    }
+
+    #[test]
+    fn removes_pairs() {
+        let mut m: SplitMap<u8, u8, 4> = SplitMap::new();
+        for i in 0..4 {
+            m.insert(i, i * 10);
+        }
+        assert_eq!(Some(10), m.remove(&1));
+        assert_eq!(None, m.remove(&1));
+        assert_eq!(Some((0, 0)), m.remove_entry(&0));
+        assert_eq!(vec![&2, &3], m.keys().collect::<Vec<_>>());
+        assert_eq!(vec![&20, &30], m.values().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn retains_in_order() {
+        let mut m: SplitMap<u8, u8, 8> = SplitMap::new();
+        for i in 0..8 {
+            m.insert(i, i);
+        }
+        assert_eq!(
+            4,
+            m.retain(|k, v| {
+                *v *= 2;
+                k % 2 == 1
+            })
+        );
+        assert_eq!(vec![&1, &3, &5, &7], m.keys().collect::<Vec<_>>());
+        assert_eq!(vec![&2, &6, &10, &14], m.values().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn iterates_pairs() {
+        let mut m: SplitMap<u8, u8, 8> = SplitMap::new();
+        m.insert(1, 10);
+        m.insert(2, 20);
+        for (_, v) in &mut m {
+            *v += 1;
+        }
+        m.values_mut().for_each(|v| *v *= 2);
+        assert_eq!(vec![(&1, &22), (&2, &42)], m.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn clones_and_compares() {
+        let m: SplitMap<u8, String, 4> = [(1, "one".to_string()), (2, "two".to_string())]
+            .into_iter()
+            .collect();
+        let c = m.clone();
+        assert!(m == c);
+        let mut d = m.clone();
+        d.insert(3, "three".to_string());
+        assert!(m != d);
+    }
+
+    #[test]
+    fn drops_keys_and_values() {
+        use std::rc::Rc;
+        let mut m: SplitMap<Rc<()>, Rc<()>, 8> = SplitMap::new();
+        let k = Rc::new(());
+        let v = Rc::new(());
+        m.insert(Rc::clone(&k), Rc::clone(&v));
+        m.retain(|_, _| true);
+        assert_eq!(2, Rc::strong_count(&k));
+        m.clear();
+        assert_eq!(1, Rc::strong_count(&k));
+        assert_eq!(1, Rc::strong_count(&v));
+        m.insert(Rc::clone(&k), Rc::clone(&v));
+        drop(m);
+        assert_eq!(1, Rc::strong_count(&k));
+        assert_eq!(1, Rc::strong_count(&v));
+    }
+
+    #[test]
+    fn takes_as_much_space_as_map() {
+        use core::mem::size_of;
+        assert_eq!(
+            size_of::<crate::Map<u32, [u8; 256], 16>>(),
+            size_of::<SplitMap<u32, [u8; 256], 16>>()
+        );
+    }
+
+    #[test]
+    fn prints_like_map() {
+        let mut m: SplitMap<&str, i32, 4> = SplitMap::new();
+        m.insert("one", 1);
+        m.insert("two", 2);
+        assert_eq!(r#"{"one": 1, "two": 2}"#, format!("{m:?}"));
+        let mut it = m.into_iter();
+        it.next();
+        assert_eq!(r#"[("two", 2)]"#, format!("{it:?}"));
+    }
+
+    #[test]
+    fn iterates_by_value() {
+        let m: SplitMap<u8, String, 4> = [(1, "a".to_string()), (2, "b".to_string())]
+            .into_iter()
+            .collect();
+        let mut it = m.into_iter();
+        assert_eq!(2, it.len());
+        assert_eq!(Some((2, "b".to_string())), it.next_back());
+        assert_eq!(Some((1, "a".to_string())), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[test]
+    fn extends_from_pairs_and_refs() {
+        let mut m: SplitMap<u8, u8, 4> = SplitMap::new();
+        m.extend([(1, 1), (2, 2)]);
+        let other: SplitMap<u8, u8, 4> = [(2, 20), (3, 30)].into_iter().collect();
+        m.extend(&other);
+        assert_eq!(3, m.len());
+        assert_eq!(20, m[&2]);
+    }
+}
@@ -0,0 +1,507 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::error::OVERFLOW;
+use crate::{CapacityError, IntoIter, Iter, IterMut, Keys, Map, TaggedMap, Values, ValuesMut};
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::mem;
+use core::ops::{Index, IndexMut};
+
+impl<K: PartialEq, V, const N: usize> TaggedMap<K, V, N> {
+    /// Make it.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            tags: [0; N],
+            map: Map::new(),
+        }
+    }
+
+    /// Get its total capacity.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Is it empty?
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Return the total number of pairs inside.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Remove all pairs from it, but keep the space intact for future use.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Make an iterator over all pairs.
+    #[inline]
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, K, V, N> {
+        self.map.iter()
+    }
+
+    /// An iterator with mutable references to the values but
+    /// immutable references to the keys.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// An iterator visiting all keys in the order of their positions.
+    #[inline]
+    #[must_use]
+    pub const fn keys(&self) -> Keys<'_, K, V, N> {
+        self.map.keys()
+    }
+
+    /// An iterator visiting all values in the order of their positions.
+    #[inline]
+    #[must_use]
+    pub const fn values(&self) -> Values<'_, K, V, N> {
+        self.map.values()
+    }
+
+    /// An iterator visiting all values mutably in the order of their positions.
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        self.map.values_mut()
+    }
+}
+
+impl<K: PartialEq + Hash, V, const N: usize> TaggedMap<K, V, N> {
+    /// Does the map contain this key?
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q: PartialEq + Hash + ?Sized>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k, tag(k)).is_some()
+    }
+
+    /// Get a reference to a single value.
+    #[inline]
+    #[must_use]
+    pub fn get<Q: PartialEq + Hash + ?Sized>(&self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k, tag(k)).map(|i| &self.map.item(i).1)
+    }
+
+    /// Get a mutable reference to a single value.
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q: PartialEq + Hash + ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k, tag(k))
+            .map(|i| &mut self.map.item_mut(i).1)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[inline]
+    pub fn get_key_value<Q: PartialEq + Hash + ?Sized>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k, tag(k)).map(|i| {
+            let p = self.map.item(i);
+            (&p.0, &p.1)
+        })
+    }
+
+    /// Insert a single pair into the map.
+    ///
+    /// If the map did have this key present, the value is updated, and
+    /// the old value is returned. The key is not updated.
+    ///
+    /// # Panics
+    ///
+    /// If there are too many pairs in the map already.
+    #[inline]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        self.try_insert(k, v)
+            .unwrap_or_else(|_| panic!("{OVERFLOW}"))
+    }
+
+    /// Try to insert a single pair into the map.
+    ///
+    /// # Errors
+    ///
+    /// If the key is absent and there is no more space in the map,
+    /// a [`CapacityError`] is returned, with the rejected pair inside.
+    #[inline]
+    pub fn try_insert(&mut self, k: K, v: V) -> Result<Option<V>, CapacityError<K, V>> {
+        let t = tag(&k);
+        if let Some(i) = self.position(&k, t) {
+            return Ok(Some(mem::replace(&mut self.map.item_mut(i).1, v)));
+        }
+        if self.map.len() == N {
+            return Err(CapacityError::new(k, v));
+        }
+        self.tags[self.map.len()] = t;
+        self.map.push(k, v);
+        Ok(None)
+    }
+
+    /// Remove by key, returning the value if the key was previously in the map.
+    ///
+    /// The last pair in the map takes the place of the removed one.
+    #[inline]
+    pub fn remove<Q: PartialEq + Hash + ?Sized>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        self.remove_entry(k).map(|p| p.1)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the
+    /// key was previously in the map.
+    #[inline]
+    pub fn remove_entry<Q: PartialEq + Hash + ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.position(k, tag(k)).map(|i| {
+            self.tags[i] = self.tags[self.map.len() - 1];
+            self.map.swap_take(i)
+        })
+    }
+
+    /// Retains only the elements specified by the predicate, which may
+    /// also modify the values.
+    ///
+    /// The order of the retained pairs doesn't change. The number of removed
+    /// pairs is returned.
+    #[inline]
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) -> usize {
+        let busy = self.map.len();
+        let mut i = 0;
+        while i < self.map.len() {
+            let p = self.map.item_mut(i);
+            if f(&p.0, &mut p.1) {
+                i += 1;
+            } else {
+                self.tags.copy_within(i + 1..self.map.len(), i);
+                drop(self.map.shift_take(i));
+            }
+        }
+        busy - self.map.len()
+    }
+
+    /// Internal function to find the position of the key, comparing
+    /// the keys only if their fingerprints are equal to `t`.
+    #[inline]
+    fn position<Q: PartialEq + ?Sized>(&self, k: &Q, t: u8) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        (0..self.map.len()).find(|&i| self.tags[i] == t && self.map.item(i).0.borrow() == k)
+    }
+}
+
+/// Calculate the one-byte fingerprint of the key, taking the highest
+/// byte of its hash, which is the best mixed one.
+#[inline]
+fn tag<Q: Hash + ?Sized>(k: &Q) -> u8 {
+    let mut h = Fx(0);
+    k.hash(&mut h);
+    h.finish().to_be_bytes()[0]
+}
+
+/// The hasher from the Firefox browser, which is very fast, since it
+/// consumes eight bytes at a time, but is not good for anything else
+/// than fingerprints and hash tables.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) struct Fx(pub(crate) u64);
+
+impl Fx {
+    /// Mix one more word into the hash.
+    #[inline]
+    const fn add(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+}
+
+impl Hasher for Fx {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut b = bytes;
+        while let Some((c, rest)) = b.split_first_chunk::<8>() {
+            self.add(u64::from_le_bytes(*c));
+            b = rest;
+        }
+        if let Some((c, rest)) = b.split_first_chunk::<4>() {
+            self.add(u64::from(u32::from_le_bytes(*c)));
+            b = rest;
+        }
+        if let Some((c, rest)) = b.split_first_chunk::<2>() {
+            self.add(u64::from(u16::from_le_bytes(*c)));
+            b = rest;
+        }
+        if let Some(c) = b.first() {
+            self.add(u64::from(*c));
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Default for TaggedMap<K, V, N> {
+    /// Make a default empty [`TaggedMap`].
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Clone + PartialEq, V: Clone, const N: usize> Clone for TaggedMap<K, V, N> {
+    fn clone(&self) -> Self {
+        Self {
+            tags: self.tags,
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<K: PartialEq + Debug, V: Debug, const N: usize> Debug for TaggedMap<K, V, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K: PartialEq + Hash, V: PartialEq, const N: usize> PartialEq for TaggedMap<K, V, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K: Eq + Hash, V: Eq, const N: usize> Eq for TaggedMap<K, V, N> {}
+
+impl<K: PartialEq + Hash, V, const N: usize> FromIterator<(K, V)> for TaggedMap<K, V, N> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut m: Self = Self::new();
+        for (k, v) in iter {
+            m.insert(k, v);
+        }
+        m
+    }
+}
+
+impl<'a, K: PartialEq, V, const N: usize> IntoIterator for &'a TaggedMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: PartialEq, V, const N: usize> IntoIterator for &'a mut TaggedMap<K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> IntoIterator for TaggedMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
+impl<K: Eq + Hash + Borrow<Q>, Q: Eq + Hash + ?Sized, V, const N: usize> Index<&Q>
+    for TaggedMap<K, V, N>
+{
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("No entry found for the key")
+    }
+}
+
+impl<K: Eq + Hash + Borrow<Q>, Q: Eq + Hash + ?Sized, V, const N: usize> IndexMut<&Q>
+    for TaggedMap<K, V, N>
+{
+    #[inline]
+    fn index_mut(&mut self, key: &Q) -> &mut V {
+        self.get_mut(key).expect("No entry found for the key")
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn insert_and_gets() {
+        let mut m: TaggedMap<String, i32, 10> = TaggedMap::new();
+        assert_eq!(None, m.insert("one".to_string(), 42));
+        assert_eq!(None, m.insert("two".to_string(), 16));
+        assert_eq!(Some(16), m.insert("two".to_string(), 17));
+        assert_eq!(2, m.len());
+        assert_eq!(Some(&42), m.get("one"));
+        assert_eq!(Some((&"two".to_string(), &17)), m.get_key_value("two"));
+        assert!(m.contains_key("one"));
+        assert!(!m.contains_key("three"));
+        m["one"] += 1;
+        assert_eq!(43, m["one"]);
+    }
+
+    #[test]
+    fn finds_keys_with_equal_tags() {
+        let keys: Vec<u32> = (0..1000).filter(|k| tag(k) == tag(&0u32)).take(5).collect();
+        assert_eq!(5, keys.len());
+        let mut m: TaggedMap<u32, u32, 8> = keys.iter().map(|k| (*k, *k * 2)).collect();
+        for k in &keys {
+            assert_eq!(Some(&(k * 2)), m.get(k));
+        }
+        m.remove(&keys[1]);
+        for k in &keys[2..] {
+            assert_eq!(Some(&(k * 2)), m.get(k));
+        }
+        assert_eq!(None, m.get(&keys[1]));
+    }
+
+    #[test]
+    fn rejects_overflow() {
+        let mut m: TaggedMap<u8, u8, 1> = TaggedMap::new();
+        m.insert(1, 1);
+        assert_eq!((2, 2), m.try_insert(2, 2).unwrap_err().into_pair());
+        assert_eq!(Some(1), m.try_insert(1, 3).unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "No more keys available in the map")]
+    fn panics_on_overflow() {
+        let mut m: TaggedMap<u8, u8, 1> = TaggedMap::new();
+        m.insert(1, 1);
+        m.insert(2, 2);
+    }
+
+    #[test]
+    fn keeps_tags_after_removals() {
+        let mut m: TaggedMap<String, usize, 16> = TaggedMap::new();
+        for i in 0..16 {
+            m.insert(format!("key-{i}"), i);
+        }
+        m.remove("key-3");
+        m.remove("key-0");
+        m.retain(|_, v| *v % 3 != 0);
+        for i in 0..16 {
+            let expected = if i % 3 == 0 { None } else { Some(&i) };
+            assert_eq!(expected, m.get(format!("key-{i}").as_str()));
+        }
+        assert_eq!(10, m.len());
+    }
+
+    #[test]
+    fn retains_in_order() {
+        let mut m: TaggedMap<u8, u8, 8> = (0..8).map(|i| (i, i)).collect();
+        assert_eq!(4, m.retain(|k, _| k % 2 == 1));
+        assert_eq!(vec![&1, &3, &5, &7], m.keys().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn clones_and_compares() {
+        let m: TaggedMap<&str, u8, 4> = [("one", 1), ("two", 2)].into_iter().collect();
+        let mut c = m.clone();
+        assert!(m == c);
+        c.insert("three", 3);
+        assert!(m != c);
+        assert_eq!(Some(&3), c.get("three"));
+    }
+
+    #[test]
+    fn iterates_pairs() {
+        let mut m: TaggedMap<u8, u8, 8> = TaggedMap::new();
+        m.insert(1, 10);
+        m.insert(2, 20);
+        for (_, v) in &mut m {
+            *v += 1;
+        }
+        assert_eq!(vec![(&1, &11), (&2, &21)], m.iter().collect::<Vec<_>>());
+        assert_eq!(vec![(1, 11), (2, 21)], m.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn prints_like_map() {
+        let mut m: TaggedMap<&str, i32, 4> = TaggedMap::new();
+        m.insert("one", 1);
+        m.insert("two", 2);
+        assert_eq!(r#"{"one": 1, "two": 2}"#, format!("{m:?}"));
+    }
+}
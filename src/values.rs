@@ -18,24 +18,28 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::{IntoValues, Map, Values, ValuesMut};
+use crate::{IntoValues, Map, SortedMap, Values, ValuesMut};
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
 
-impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
-    /// An iterator visiting all values in arbitrary order.
+impl<K: PartialEq, V, const N: usize, E> Map<K, V, N, E> {
+    /// An iterator visiting all values in the order of their positions.
     #[inline]
     pub const fn values(&self) -> Values<'_, K, V, N> {
         Values { iter: self.iter() }
     }
 
-    /// An iterator visiting all values mutably in arbitrary order.
+    /// An iterator visiting all values mutably in the order of their positions.
     #[inline]
     pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
         ValuesMut {
             iter: self.iter_mut(),
         }
     }
+}
 
-    /// Consuming iterator visiting all the values in arbitrary order.
+impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
+    /// Consuming iterator visiting all the values in the order of their positions.
     #[inline]
     pub fn into_values(self) -> IntoValues<K, V, N> {
         IntoValues {
@@ -44,6 +48,28 @@ impl<K: PartialEq, V, const N: usize> Map<K, V, N> {
     }
 }
 
+impl<K: Ord, V, const N: usize> SortedMap<K, V, N> {
+    /// An iterator visiting all values in the order of their keys.
+    #[inline]
+    #[must_use]
+    pub const fn values(&self) -> Values<'_, K, V, N> {
+        self.map.values()
+    }
+
+    /// An iterator visiting all values mutably in the order of their keys.
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        self.map.values_mut()
+    }
+
+    /// Consumes the map and returns an iterator over all values,
+    /// in the order of their keys.
+    #[inline]
+    pub fn into_values(self) -> IntoValues<K, V, N> {
+        self.map.into_values()
+    }
+}
+
 impl<'a, K: PartialEq, V, const N: usize> Iterator for Values<'a, K, V, N> {
     type Item = &'a V;
 
@@ -51,6 +77,37 @@ impl<'a, K: PartialEq, V, const N: usize> Iterator for Values<'a, K, V, N> {
     fn next(&mut self) -> Option<Self::Item> {
         self.iter.next().map(|p| p.1)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> DoubleEndedIterator for Values<'_, K, V, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|p| p.1)
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> ExactSizeIterator for Values<'_, K, V, N> {}
+
+impl<K: PartialEq, V, const N: usize> FusedIterator for Values<'_, K, V, N> {}
+
+impl<K: PartialEq, V, const N: usize> Clone for Values<'_, K, V, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<K: PartialEq, V: Debug, const N: usize> Debug for Values<'_, K, V, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
 }
 
 impl<'a, K: PartialEq, V> Iterator for ValuesMut<'a, K, V> {
@@ -60,6 +117,30 @@ impl<'a, K: PartialEq, V> Iterator for ValuesMut<'a, K, V> {
     fn next(&mut self) -> Option<Self::Item> {
         self.iter.next().map(|p| p.1)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K: PartialEq, V> DoubleEndedIterator for ValuesMut<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|p| p.1)
+    }
+}
+
+impl<K: PartialEq, V> ExactSizeIterator for ValuesMut<'_, K, V> {}
+
+impl<K: PartialEq, V> FusedIterator for ValuesMut<'_, K, V> {}
+
+impl<K: PartialEq, V: Debug> Debug for ValuesMut<'_, K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter.as_slice().iter().map(|p| &p.1))
+            .finish()
+    }
 }
 
 impl<K: PartialEq, V, const N: usize> Iterator for IntoValues<K, V, N> {
@@ -69,6 +150,30 @@ impl<K: PartialEq, V, const N: usize> Iterator for IntoValues<K, V, N> {
     fn next(&mut self) -> Option<V> {
         self.iter.next().map(|p| p.1)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> DoubleEndedIterator for IntoValues<K, V, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<V> {
+        self.iter.next_back().map(|p| p.1)
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> ExactSizeIterator for IntoValues<K, V, N> {}
+
+impl<K: PartialEq, V, const N: usize> FusedIterator for IntoValues<K, V, N> {}
+
+impl<K: PartialEq, V: Debug, const N: usize> Debug for IntoValues<K, V, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter.as_slice().iter().map(|p| &p.1))
+            .finish()
+    }
 }
 
 #[cfg(test)]
@@ -81,7 +186,7 @@ mod test {
         let mut m: Map<&str, i32, 10> = Map::new();
         m.insert("one", 42);
         m.insert("two", 16);
-        assert_eq!(58, m.values().sum());
+        assert_eq!(58, m.values().sum::<i32>());
     }
 
     #[test]
@@ -90,7 +195,7 @@ mod test {
         m.insert("one", 42);
         m.insert("two", 16);
         m.values_mut().for_each(|v| *v *= 2);
-        assert_eq!(116, m.values().sum());
+        assert_eq!(116, m.values().sum::<i32>());
     }
 
     #[test]
@@ -103,6 +208,27 @@ mod test {
         assert_eq!(m.values().collect::<Vec<_>>(), [&1, &5]);
     }
 
+    #[test]
+    fn iterate_values_backwards() {
+        let mut m: Map<&str, i32, 10> = Map::new();
+        m.insert("one", 1);
+        m.insert("two", 3);
+        m.insert("three", 5);
+        m.remove(&"one");
+        assert_eq!(m.values().rev().collect::<Vec<_>>(), [&3, &5]);
+        assert_eq!("[5, 3]", format!("{:?}", m.values()));
+        let mut values = m.values_mut();
+        assert_eq!(2, values.len());
+        *values.next_back().unwrap() += 1;
+        assert_eq!("[5]", format!("{values:?}"));
+        let mut into = m.into_values();
+        assert_eq!((2, Some(2)), into.size_hint());
+        assert_eq!(Some(4), into.next_back());
+        assert_eq!("[5]", format!("{into:?}"));
+        assert_eq!(Some(5), into.next());
+        assert_eq!(None, into.next_back());
+    }
+
     #[test]
     fn into_values_drop() {
         use std::rc::Rc;
@@ -21,12 +21,18 @@
 // In order to run this single test from the command line:
 // $ cargo test --test benchmark -- --nocapture
 
+#![allow(clippy::reversed_empty_ranges)]
+
 use std::collections::HashMap;
 use std::env;
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 const CAPACITY: usize = 1;
 
+/// The tests measuring time run one at a time, not to slow each other down.
+static CLOCK: Mutex<()> = Mutex::new(());
+
 macro_rules! eval {
     ($map:expr, $total:expr, $capacity:expr) => {{
         let mut sum = 0;
@@ -41,6 +47,7 @@ macro_rules! eval {
             for i in 1..$capacity - 1 {
                 $map.remove(&(i as u32));
             }
+            assert_eq!(1, std::hint::black_box($map.len()));
             if $map.iter().find(|(_k, v)| **v == 0).is_some() {
                 $map.clear();
             }
@@ -142,6 +149,7 @@ fn benchmark(total: usize) -> HashMap<&'static str, Duration> {
 /// ```
 #[test]
 pub fn benchmark_and_print() {
+    let _clock = CLOCK.lock().unwrap();
     let times = benchmark(
         #[cfg(debug_assertions)]
         100000,
@@ -162,6 +170,81 @@ pub fn benchmark_and_print() {
     println!("Total gain: {:.2}", total);
 }
 
+/// The layout of [`micromap::Map`] before the pairs were packed together:
+/// a removed pair leaves a `None` behind, which lookups, iterations and
+/// `len()` have to skip.
+struct Tombstones<const N: usize> {
+    next: usize,
+    pairs: [Option<(u32, i64)>; N],
+}
+
+impl<const N: usize> Tombstones<N> {
+    const fn new() -> Self {
+        Self {
+            next: 0,
+            pairs: [None; N],
+        }
+    }
+
+    fn insert(&mut self, k: u32, v: i64) {
+        self.pairs[self.next] = Some((k, v));
+        self.next += 1;
+    }
+
+    fn remove(&mut self, k: &u32) {
+        for p in &mut self.pairs[..self.next] {
+            if p.is_some_and(|(x, _)| x == *k) {
+                *p = None;
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.pairs[..self.next].iter().flatten().count()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &(u32, i64)> {
+        self.pairs[..self.next].iter().flatten()
+    }
+}
+
+macro_rules! after_removals {
+    ($map:expr, $total:expr) => {{
+        let start = Instant::now();
+        let mut m = $map;
+        for i in 0..64 {
+            m.insert(i, i64::from(i));
+        }
+        for i in 0..64 {
+            if i % 8 != 0 {
+                m.remove(&i);
+            }
+        }
+        let mut sum = 0;
+        for _ in 0..$total {
+            sum += std::hint::black_box(m.len()) as i64;
+            for p in m.iter() {
+                sum += std::hint::black_box(p).1;
+            }
+        }
+        assert_eq!(sum, $total * (8 + 224));
+        start.elapsed()
+    }};
+}
+
+/// Packed pairs are counted and iterated without skipping the holes
+/// left by removals, which the old layout with tombstones had to skip.
+#[test]
+#[allow(clippy::explicit_iter_loop)]
+pub fn packed_pairs_beat_tombstones() {
+    let _clock = CLOCK.lock().unwrap();
+    let total: i64 = 100000;
+    let theirs = after_removals!(Tombstones::<64>::new(), total);
+    let ours = after_removals!(micromap::Map::<u32, i64, 64>::new(), total);
+    println!("tombstones -> {theirs:?}, micromap::Map -> {ours:?}");
+    assert!(theirs.cmp(&ours).is_gt());
+}
+
 pub fn main() {
     let args: Vec<String> = env::args().collect();
     let times = benchmark(args.get(1).unwrap().parse::<usize>().unwrap());
@@ -0,0 +1,410 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// These tests count how many times keys and values are dropped, in order
+// to make sure that every removing path drops every pair exactly once.
+// They are cheap enough to be executed under Miri:
+// $ cargo +nightly miri test --test drops
+
+use micromap::{Entry, Map, SplitMap};
+use std::cell::Cell;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::rc::Rc;
+
+/// A value, which counts its own destructions in a shared counter.
+struct Counted {
+    id: u32,
+    drops: Rc<Cell<usize>>,
+    explosive: bool,
+}
+
+impl Counted {
+    fn new(id: u32, drops: &Rc<Cell<usize>>) -> Self {
+        Self {
+            id,
+            drops: Rc::clone(drops),
+            explosive: false,
+        }
+    }
+
+    fn explosive(id: u32, drops: &Rc<Cell<usize>>) -> Self {
+        Self {
+            id,
+            drops: Rc::clone(drops),
+            explosive: true,
+        }
+    }
+}
+
+impl Clone for Counted {
+    fn clone(&self) -> Self {
+        Self::new(self.id, &self.drops)
+    }
+}
+
+impl PartialEq for Counted {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+        if self.explosive {
+            panic!("Boom!");
+        }
+    }
+}
+
+fn full(drops: &Rc<Cell<usize>>) -> Map<Counted, Counted, 8> {
+    let mut m = Map::new();
+    for i in 0..8 {
+        m.insert(Counted::new(i, drops), Counted::new(i, drops));
+    }
+    m
+}
+
+#[test]
+fn drops_everything_with_map() {
+    let drops = Rc::new(Cell::new(0));
+    drop(full(&drops));
+    assert_eq!(16, drops.get());
+}
+
+#[test]
+fn drops_on_remove() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m = full(&drops);
+    let v = m.remove(&Counted::new(3, &drops)).unwrap();
+    assert_eq!(2, drops.get());
+    drop(v);
+    assert_eq!(3, drops.get());
+    assert!(m.remove(&Counted::new(3, &drops)).is_none());
+    assert_eq!(4, drops.get());
+    drop(m);
+    assert_eq!(18, drops.get());
+}
+
+#[test]
+fn drops_on_remove_entry() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m = full(&drops);
+    let p = m.remove_entry(&Counted::new(0, &drops)).unwrap();
+    assert_eq!(1, drops.get());
+    drop(p);
+    assert_eq!(3, drops.get());
+    drop(m);
+    assert_eq!(17, drops.get());
+}
+
+#[test]
+fn drops_on_clear() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m = full(&drops);
+    m.clear();
+    assert_eq!(16, drops.get());
+    assert!(m.is_empty());
+    drop(m);
+    assert_eq!(16, drops.get());
+}
+
+#[test]
+fn drops_on_retain() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m = full(&drops);
+    m.retain(|k, _| k.id % 2 == 0);
+    assert_eq!(8, drops.get());
+    assert_eq!(4, m.len());
+    drop(m);
+    assert_eq!(16, drops.get());
+}
+
+#[test]
+fn drops_on_overwrite() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m = full(&drops);
+    let old = m.insert(Counted::new(5, &drops), Counted::new(50, &drops));
+    assert_eq!(1, drops.get());
+    assert_eq!(5, old.unwrap().id);
+    assert_eq!(2, drops.get());
+    drop(m);
+    assert_eq!(18, drops.get());
+}
+
+#[test]
+fn drops_rejected_pair() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m = full(&drops);
+    let e = m.try_insert(Counted::new(9, &drops), Counted::new(9, &drops));
+    assert_eq!(0, drops.get());
+    drop(e);
+    assert_eq!(2, drops.get());
+    drop(m);
+    assert_eq!(18, drops.get());
+}
+
+#[test]
+fn drops_on_partial_into_iter() {
+    let drops = Rc::new(Cell::new(0));
+    let m = full(&drops);
+    let mut iter = m.into_iter();
+    let p = iter.next().unwrap();
+    drop(iter);
+    assert_eq!(14, drops.get());
+    drop(p);
+    assert_eq!(16, drops.get());
+}
+
+#[test]
+fn drops_on_partial_into_keys_and_values() {
+    let drops = Rc::new(Cell::new(0));
+    let k = full(&drops).into_keys().next().unwrap();
+    assert_eq!(15, drops.get());
+    drop(k);
+    let v = full(&drops).into_values().nth(2).unwrap();
+    assert_eq!(31, drops.get());
+    drop(v);
+    assert_eq!(32, drops.get());
+}
+
+#[test]
+fn drops_on_entry_removal() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m = full(&drops);
+    match m.entry(Counted::new(2, &drops)) {
+        Entry::Occupied(e) => drop(e.remove()),
+        Entry::Vacant(_) => unreachable!(),
+    }
+    assert_eq!(3, drops.get());
+    drop(m);
+    assert_eq!(17, drops.get());
+}
+
+#[test]
+fn drops_after_remove_and_insert() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m = full(&drops);
+    for i in 0..8 {
+        m.remove(&Counted::new(i, &drops));
+        m.insert(Counted::new(i + 10, &drops), Counted::new(i, &drops));
+    }
+    assert_eq!(24, drops.get());
+    drop(m);
+    assert_eq!(40, drops.get());
+}
+
+#[test]
+fn drops_clones_separately() {
+    let mut m: Map<u32, Rc<()>, 4> = Map::new();
+    let v = Rc::new(());
+    m.insert(1, Rc::clone(&v));
+    m.insert(2, Rc::clone(&v));
+    let c = m.clone();
+    assert_eq!(5, Rc::strong_count(&v));
+    drop(m);
+    assert_eq!(3, Rc::strong_count(&v));
+    drop(c);
+    assert_eq!(1, Rc::strong_count(&v));
+}
+
+#[test]
+fn drops_everything_when_destructor_panics_in_clear() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m: Map<u32, Counted, 4> = Map::new();
+    m.insert(1, Counted::new(1, &drops));
+    m.insert(2, Counted::explosive(2, &drops));
+    m.insert(3, Counted::new(3, &drops));
+    let r = panic::catch_unwind(AssertUnwindSafe(|| m.clear()));
+    assert!(r.is_err());
+    assert_eq!(3, drops.get());
+    assert!(m.is_empty());
+    drop(m);
+    assert_eq!(3, drops.get());
+}
+
+#[test]
+fn drops_everything_when_destructor_panics_in_retain() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m: Map<u32, Counted, 4> = Map::new();
+    m.insert(1, Counted::new(1, &drops));
+    m.insert(2, Counted::explosive(2, &drops));
+    m.insert(3, Counted::new(3, &drops));
+    let r = panic::catch_unwind(AssertUnwindSafe(|| m.retain(|_, _| false)));
+    assert!(r.is_err());
+    assert_eq!(2, drops.get());
+    drop(m);
+    assert_eq!(3, drops.get());
+}
+
+#[test]
+fn drops_the_rest_when_drain_is_dropped() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m = full(&drops);
+    let taken: Vec<_> = m.drain().take(3).collect();
+    assert_eq!(10, drops.get());
+    assert!(m.is_empty());
+    drop(taken);
+    assert_eq!(16, drops.get());
+    drop(m);
+    assert_eq!(16, drops.get());
+}
+
+#[test]
+fn drops_everything_when_destructor_panics_in_drain() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m: Map<u32, Counted, 4> = Map::new();
+    m.insert(1, Counted::new(1, &drops));
+    m.insert(2, Counted::explosive(2, &drops));
+    m.insert(3, Counted::new(3, &drops));
+    let r = panic::catch_unwind(AssertUnwindSafe(|| drop(m.drain())));
+    assert!(r.is_err());
+    assert_eq!(3, drops.get());
+    assert!(m.is_empty());
+    drop(m);
+    assert_eq!(3, drops.get());
+}
+
+#[test]
+fn keeps_unvisited_pairs_when_extract_if_is_dropped() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m = full(&drops);
+    let first = m.extract_if(|k, _| k.id % 2 == 1).next();
+    assert_eq!(1, first.unwrap().0.id);
+    assert_eq!(2, drops.get());
+    assert_eq!(7, m.len());
+    assert_eq!(
+        vec![0, 2, 3, 4, 5, 6, 7],
+        m.keys().map(|k| k.id).collect::<Vec<_>>()
+    );
+    drop(m);
+    assert_eq!(16, drops.get());
+}
+
+#[test]
+fn keeps_unvisited_pairs_when_predicate_panics_in_extract_if() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m = full(&drops);
+    let r = panic::catch_unwind(AssertUnwindSafe(|| {
+        m.extract_if(|k, _| {
+            assert!(k.id != 4, "Boom!");
+            k.id % 2 == 0
+        })
+        .count()
+    }));
+    assert!(r.is_err());
+    assert_eq!(4, drops.get());
+    assert_eq!(
+        vec![1, 3, 4, 5, 6, 7],
+        m.keys().map(|k| k.id).collect::<Vec<_>>()
+    );
+    drop(m);
+    assert_eq!(16, drops.get());
+}
+
+#[test]
+fn drops_everything_when_key_destructor_panics_in_split_clear() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m: SplitMap<Counted, Counted, 4> = SplitMap::new();
+    m.insert(Counted::new(1, &drops), Counted::new(1, &drops));
+    m.insert(Counted::explosive(2, &drops), Counted::new(2, &drops));
+    m.insert(Counted::new(3, &drops), Counted::new(3, &drops));
+    let r = panic::catch_unwind(AssertUnwindSafe(|| m.clear()));
+    assert!(r.is_err());
+    assert_eq!(6, drops.get());
+    assert!(m.is_empty());
+    drop(m);
+    assert_eq!(6, drops.get());
+}
+
+#[test]
+fn drops_everything_when_destructor_panics_in_split_retain() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m: SplitMap<u32, Counted, 4> = SplitMap::new();
+    m.insert(1, Counted::new(1, &drops));
+    m.insert(2, Counted::explosive(2, &drops));
+    m.insert(3, Counted::new(3, &drops));
+    let r = panic::catch_unwind(AssertUnwindSafe(|| m.retain(|_, _| false)));
+    assert!(r.is_err());
+    assert_eq!(2, drops.get());
+    assert_eq!(vec![3], m.keys().copied().collect::<Vec<_>>());
+    drop(m);
+    assert_eq!(3, drops.get());
+}
+
+#[test]
+fn keeps_unvisited_pairs_when_predicate_panics_in_split_retain() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m: SplitMap<Counted, Counted, 8> = SplitMap::new();
+    for i in 0..8 {
+        m.insert(Counted::new(i, &drops), Counted::new(i, &drops));
+    }
+    let r = panic::catch_unwind(AssertUnwindSafe(|| {
+        m.retain(|k, _| {
+            assert!(k.id != 4, "Boom!");
+            k.id % 2 == 1
+        })
+    }));
+    assert!(r.is_err());
+    assert_eq!(4, drops.get());
+    assert_eq!(
+        vec![1, 3, 4, 5, 6, 7],
+        m.keys().map(|k| k.id).collect::<Vec<_>>()
+    );
+    drop(m);
+    assert_eq!(16, drops.get());
+}
+
+/// A value, which can't be cloned.
+struct Fragile;
+
+impl Clone for Fragile {
+    fn clone(&self) -> Self {
+        panic!("Boom!");
+    }
+}
+
+#[test]
+fn drops_cloned_key_when_value_clone_panics_in_split_map() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m: SplitMap<Counted, Fragile, 2> = SplitMap::new();
+    m.insert(Counted::new(1, &drops), Fragile);
+    let r = panic::catch_unwind(AssertUnwindSafe(|| m.clone()));
+    assert!(r.is_err());
+    assert_eq!(1, drops.get());
+    drop(m);
+    assert_eq!(2, drops.get());
+}
+
+#[test]
+fn drops_the_rest_of_split_into_iter() {
+    let drops = Rc::new(Cell::new(0));
+    let mut m: SplitMap<Counted, Counted, 8> = SplitMap::new();
+    for i in 0..8 {
+        m.insert(Counted::new(i, &drops), Counted::new(i, &drops));
+    }
+    let mut it = m.into_iter();
+    drop(it.next());
+    drop(it.next_back());
+    assert_eq!(4, drops.get());
+    drop(it);
+    assert_eq!(16, drops.get());
+}
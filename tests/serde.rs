@@ -0,0 +1,85 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// These tests make sure that maps survive the round trip through
+// different formats, both as maps and as sequences of pairs.
+
+#![cfg(feature = "serde")]
+
+use micromap::Map;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+enum Color {
+    Red,
+    Green,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Doc {
+    names: Map<String, u32, 4>,
+    #[serde(with = "micromap::serde::as_pairs")]
+    cells: Map<(u8, u8), Color, 4>,
+    #[serde(with = "micromap::serde::as_pairs")]
+    colors: Map<Color, Vec<u8>, 2>,
+}
+
+fn doc() -> Doc {
+    Doc {
+        names: Map::from([("x".to_string(), 1), ("y".to_string(), 2)]),
+        cells: Map::from([((0, 0), Color::Red), ((1, 2), Color::Green)]),
+        colors: Map::from([(Color::Green, vec![0, 255, 0])]),
+    }
+}
+
+#[test]
+fn roundtrips_through_json() {
+    let json = serde_json::to_string(&doc()).unwrap();
+    assert_eq!(
+        r#"{"names":{"x":1,"y":2},"cells":[[[0,0],"Red"],[[1,2],"Green"]],"colors":[["Green",[0,255,0]]]}"#,
+        json
+    );
+    assert_eq!(doc(), serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn roundtrips_through_bincode() {
+    let bytes = bincode::serialize(&doc()).unwrap();
+    assert_eq!(doc(), bincode::deserialize::<Doc>(&bytes).unwrap());
+}
+
+#[test]
+fn reads_pairs_from_json_map() {
+    let json = r#"{"names":[["x",1]],"cells":[],"colors":{"Red":[1]}}"#;
+    let d: Doc = serde_json::from_str(json).unwrap();
+    assert_eq!(Some(&1), d.names.get("x"));
+    assert_eq!(Some(&vec![1]), d.colors.get(&Color::Red));
+}
+
+#[test]
+fn rejects_too_many_pairs() {
+    let json = r#"{"names":{"a":1,"b":2,"c":3,"d":4,"e":5},"cells":[],"colors":[]}"#;
+    let e = serde_json::from_str::<Doc>(json).unwrap_err();
+    assert!(
+        e.to_string()
+            .contains("expected a Map with at most 4 pairs"),
+        "{e}"
+    );
+}